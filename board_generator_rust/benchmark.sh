@@ -0,0 +1,28 @@
+#!/bin/bash
+#
+# Times how long a revision of the generator takes to generate boards.
+# The revision is copied into a temporary folder, and generates the
+# given number of boards without logging them. The revision needs the
+# `--number-of-boards` and `--quiet` flags.
+#
+# Usage: ./benchmark.sh [revision] [number of boards]
+# Ex:    ./benchmark.sh HEAD 10000
+#
+set -e
+
+REVISION=${1:-HEAD}
+NUMBER_OF_BOARDS=${2:-10000}
+
+REPO_DIR=$(git -C "$(dirname "$0")" rev-parse --show-toplevel)
+BENCHMARK_DIR=$(mktemp -d)
+trap 'rm -rf "$BENCHMARK_DIR"' EXIT
+
+git -C "$REPO_DIR" archive "$REVISION" board_generator_rust \
+  | tar -x --strip-components=1 -C "$BENCHMARK_DIR"
+cd "$BENCHMARK_DIR"
+
+# Shared between runs, so the dependencies are only built once
+export CARGO_TARGET_DIR=${TMPDIR:-/tmp}/rust_wordgame_benchmark
+cargo build --quiet --release
+"$CARGO_TARGET_DIR/release/rust_wordgame" --mode generate \
+  --number-of-boards "$NUMBER_OF_BOARDS" --quiet | grep "It took"
@@ -0,0 +1,5 @@
+mod difficulty;
+mod slot_ambiguity;
+
+pub use difficulty::*;
+pub use slot_ambiguity::*;
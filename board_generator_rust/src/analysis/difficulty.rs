@@ -0,0 +1,49 @@
+use crate::dictionary::WILDCARD_LETTER;
+use crate::generator::GeneratedBoard;
+use crate::render::BoardLayout;
+use crate::utils::word_length;
+
+///
+/// Scores how hard a board is to solve. The score is only meaningful
+/// when compared to other boards, and is built up from:
+///
+/// - How many words have to be found
+/// - How long those words are
+/// - How many letters are on the wheel, and how many are wildcards
+/// - How few letters are shared between words. Every found word
+///   reveals the letters it shares with the words crossing it.
+/// - How many slots are ambiguous (more than one word fits)
+///
+pub fn get_difficulty(board: &GeneratedBoard) -> f32 {
+    let layout = BoardLayout::new(board);
+    let number_of_words = board.used.len() as f32;
+    if number_of_words == 0.0 {
+        return 0.0;
+    }
+
+    let average_word_length = board
+        .used
+        .keys()
+        .map(|word| word_length(word))
+        .sum::<usize>() as f32
+        / number_of_words;
+    let number_of_wildcards = board
+        .letters
+        .chars()
+        .filter(|letter| *letter == WILDCARD_LETTER)
+        .count() as f32;
+    let number_of_intersections = layout
+        .words_per_cell
+        .values()
+        .filter(|count| **count > 1)
+        .count() as f32;
+    let unshared_cells =
+        1.0 - number_of_intersections / layout.letters.len().max(1) as f32;
+
+    number_of_words
+        + (average_word_length - 4.0) * 3.0
+        + word_length(&board.letters) as f32
+        + number_of_wildcards * 3.0
+        + unshared_cells * 10.0
+        + board.ambiguous_slots.len() as f32
+}
@@ -0,0 +1,156 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::board::{BoardError, BoardManager};
+use crate::dictionary::{DictionaryManager, WILDCARD_LETTER};
+use crate::generator::GeneratedBoard;
+
+///
+/// What the generator should do with a board that has
+/// too many ambiguous slots.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmbiguityAction {
+    /// Don't look for ambiguous slots at all, so boards never
+    /// have any `ambiguous_slots`
+    #[default]
+    Ignore,
+    /// Throw the board away and try again
+    Reject,
+    /// Keep the board, but mark it as too ambiguous
+    Flag,
+}
+
+///
+/// A slot (a placed word) on the board, which more than one of the
+/// board's words could fill. The player only sees blank cells, and the
+/// letters of crossing words they've already found. So any word of
+/// the same length which matches those crossing letters looks like
+/// a valid answer.
+///
+/// Example: The slot for "mess" crosses another word at the "m". Then
+/// "miss" also fits the slot's pattern of `"m???"`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmbiguousSlot {
+    /// The word that was actually placed in the slot
+    pub word: String,
+    /// The slot with only the crossing letters revealed. Ex: `"m???"`
+    pub pattern: String,
+    /// The other words which also fit the pattern
+    pub candidates: Vec<String>,
+}
+
+///
+/// Finds every slot on the board which has more than one answer.
+///
+/// Candidates come from all of the words that can be built from the
+/// board's letters (`all`). A candidate that is placed
+/// somewhere else on the board isn't counted, since typing it would
+/// fill in its own slot instead.
+///
+/// The slots are returned in alphabetical order of their words.
+/// Fails if the board itself isn't valid.
+/// See `BoardManager::from_generated_board()`.
+///
+pub fn find_ambiguous_slots(
+    board: &GeneratedBoard,
+) -> Result<Vec<AmbiguousSlot>, BoardError> {
+    let bm = BoardManager::from_generated_board(board)?;
+
+    // Only the board's own words can be candidates
+    let mut possible_words = DictionaryManager::new();
+    possible_words.load_words(
+        board
+            .all
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .sorted()
+            .dedup()
+            .map(String::from)
+            .collect(),
+    );
+
+    let mut ambiguous_slots: Vec<AmbiguousSlot> = bm
+        .get_used_word_objects()
+        .into_iter()
+        .filter_map(|placed_word| {
+            // Only the letters of crossings are revealed
+            let pattern: String = placed_word
+                .cells
+                .iter()
+                .map(|cell| match bm.get_cell_at(cell.row, cell.col) {
+                    Some(contents)
+                        if contents.is_used_horizontally()
+                            && contents.is_used_vertically() =>
+                    {
+                        cell.letter
+                    }
+                    _ => WILDCARD_LETTER,
+                })
+                .collect();
+
+            let candidates: Vec<String> = possible_words
+                .find_words_matching_pattern(&pattern, None)
+                .into_iter()
+                .filter(|candidate| {
+                    *candidate != placed_word.word
+                        && !bm.is_word_placed(candidate)
+                })
+                .collect();
+
+            (!candidates.is_empty()).then_some(AmbiguousSlot {
+                word: placed_word.word,
+                pattern,
+                candidates,
+            })
+        })
+        .collect();
+    ambiguous_slots.sort_by(|a, b| a.word.cmp(&b.word));
+
+    Ok(ambiguous_slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::test_utils::{make_board, make_used};
+
+    #[test]
+    fn test_find_ambiguous_slots() {
+        // "misuses" across the top, "mess" down from its "m",
+        // and "sums" down from its last "s".
+        let board = GeneratedBoard {
+            all: "emus_mess_miss_muse_sums_uses".to_owned(),
+            ..make_board(
+                7,
+                &[
+                    ("misuses", 0, 0, Direction::H),
+                    ("mess", 0, 0, Direction::V),
+                    ("sums", 0, 6, Direction::V),
+                ],
+            )
+        };
+
+        // "sums" is the only 4 letter word which starts with an "s"
+        assert_eq!(
+            find_ambiguous_slots(&board).unwrap(),
+            vec![AmbiguousSlot {
+                word: "mess".to_owned(),
+                pattern: "m???".to_owned(),
+                candidates: vec!["miss".to_owned(), "muse".to_owned()],
+            }]
+        );
+
+        // "sums" runs off the bottom of the grid
+        let malformed = GeneratedBoard {
+            used: make_used(&[("sums", 5, 6, Direction::V)]),
+            ..board
+        };
+        assert!(matches!(
+            find_ambiguous_slots(&malformed),
+            Err(BoardError::WordOutOfBounds { .. })
+        ));
+    }
+}
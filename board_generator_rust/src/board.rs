@@ -1,8 +1,12 @@
+mod board_error;
+mod board_history;
 mod board_manager;
 mod cell_contents;
 mod direction;
 mod placed_word;
 
+pub use board_error::*;
+pub use board_history::Snapshot;
 pub use board_manager::*;
 pub use cell_contents::*;
 pub use direction::*;
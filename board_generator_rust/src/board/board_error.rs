@@ -0,0 +1,95 @@
+use std::fmt;
+
+///
+/// Why a `GeneratedBoard` couldn't be turned back into a `BoardManager`,
+/// or why a snapshot couldn't be restored. Rows and columns are indexes
+/// into the board's grid.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    /// Every row of the grid must be as long as the grid is tall
+    GridNotSquare {
+        row: usize,
+        length: usize,
+        dimensions: usize,
+    },
+    /// Every placed word needs at least two letters
+    WordTooShort {
+        word: String,
+        row: usize,
+        col: usize,
+    },
+    /// The word would run off the edge of the grid
+    WordOutOfBounds {
+        word: String,
+        row: usize,
+        col: usize,
+    },
+    /// The grid has a different letter where the word should be
+    LetterMismatch {
+        word: String,
+        row: usize,
+        col: usize,
+        expected: char,
+        found: char,
+    },
+    /// Two words use the same cell in the same direction
+    OverlappingWords {
+        word: String,
+        row: usize,
+        col: usize,
+    },
+    /// The grid has a letter which isn't part of any placed word
+    UnusedLetter {
+        row: usize,
+        col: usize,
+        letter: char,
+    },
+    /// The board was cleared, or the operations after the snapshot
+    /// were undone and replaced, since the snapshot was taken
+    UnknownSnapshot,
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::GridNotSquare {
+                row,
+                length,
+                dimensions,
+            } => write!(
+                f,
+                "row {row} has {length} cells, but the grid has {dimensions} rows"
+            ),
+            BoardError::WordTooShort { word, row, col } => {
+                write!(f, "'{word}' at ({row},{col}) is shorter than 2 letters")
+            }
+            BoardError::WordOutOfBounds { word, row, col } => {
+                write!(f, "'{word}' at ({row},{col}) runs off the grid")
+            }
+            BoardError::LetterMismatch {
+                word,
+                row,
+                col,
+                expected,
+                found,
+            } => write!(
+                f,
+                "'{word}' needs '{expected}' at ({row},{col}), but the grid has '{found}'"
+            ),
+            BoardError::OverlappingWords { word, row, col } => write!(
+                f,
+                "'{word}' overlaps another word in the same direction at ({row},{col})"
+            ),
+            BoardError::UnusedLetter { row, col, letter } => write!(
+                f,
+                "'{letter}' at ({row},{col}) isn't part of any placed word"
+            ),
+            BoardError::UnknownSnapshot => {
+                write!(f, "the snapshot is no longer part of the board's history")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
@@ -0,0 +1,49 @@
+use super::cell_contents::CellContents;
+use super::placed_word::PlacedWord;
+
+///
+/// A checkpoint of a board, which the board can be restored to.
+/// See `BoardManager::snapshot()`.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// How many operations had been applied
+    pub(super) position: usize,
+    /// The id of the last operation that had been applied (or of the
+    /// reset, if there wasn't one). If the history has changed since,
+    /// the snapshot can't be restored.
+    pub(super) last_operation_id: u64,
+}
+
+///
+/// One change made to the board (placing or removing a word),
+/// which can be undone and redone. It keeps the cells it changed
+/// as they were before and after, so undoing it is always exact.
+///
+#[derive(Clone, Debug)]
+pub struct BoardOperation {
+    pub id: u64,
+    pub word: String,
+    /// The word's entry in `words_placed`, before and after
+    pub placed_before: Option<PlacedWord>,
+    pub placed_after: Option<PlacedWord>,
+    /// (row, col, contents) of each cell, before and after
+    pub cells_before: Vec<(usize, usize, CellContents)>,
+    pub cells_after: Vec<(usize, usize, CellContents)>,
+}
+
+///
+/// Every operation applied to a board since it was last reset.
+/// Undone operations are kept (after `applied`) so they can be
+/// redone, until a new operation is applied.
+///
+#[derive(Clone, Debug, Default)]
+pub struct BoardHistory {
+    pub operations: Vec<BoardOperation>,
+    /// How many of the operations are currently applied
+    pub applied: usize,
+    /// The id of the last reset (clearing the board)
+    pub reset_id: u64,
+    /// Operations (and resets) are numbered from this counter
+    pub next_id: u64,
+}
@@ -1,13 +1,20 @@
 use itertools::Itertools;
 
+use super::board_error::BoardError;
+use super::board_history::{BoardHistory, BoardOperation, Snapshot};
 use super::cell_contents::CellContents;
-use super::direction::{get_opposite_direction, Direction};
+use super::direction::Direction;
 use super::placed_word::{PlacedWord, PlacedWordCell, PossibleWordLocation};
-use crate::generator::{WordLocationOnBoard, WordLocationsOnBoard};
-use crate::utils::{all_indicies_of, shuffle};
-use std::collections::HashMap;
+use crate::generator::{
+    GeneratedBoard, WordLocationOnBoard, WordLocationsOnBoard,
+};
+use crate::utils::{all_indicies_of, shuffle, word_length};
+use std::collections::{BTreeSet, HashMap};
 
-const EMPTY_CELL_VALUE: char = '#';
+/// The letter used to represent a cell that has no letter in it.
+pub const EMPTY_CELL_VALUE: char = '#';
+/// The letter drawn for a cell whose letter the player hasn't found yet.
+pub const HIDDEN_CELL_VALUE: char = '_';
 
 #[derive(Debug, Default)]
 pub struct BoardManager {
@@ -19,8 +26,17 @@ pub struct BoardManager {
     /// "has this word been placed yet", and if so "where is it and in which
     /// direction is it placed".
     words_placed: HashMap<String, PlacedWord>,
-    /// A 2d vector. Conceptually it represents "the board".
-    grid: Vec<Vec<CellContents>>,
+    /// Conceptually it represents "the board". The rows of the grid
+    /// are stored one after the other, in a single (flat) vector.
+    /// See `cell_index()`.
+    grid: Vec<CellContents>,
+    /// The index (within the grid) of every occupied cell, by its
+    /// letter. Finding where a word could cross the board only needs
+    /// to look at the cells with the word's letters.
+    cells_by_letter: HashMap<char, BTreeSet<usize>>,
+    /// Every word placed or removed since the board was last reset,
+    /// so they can be undone and redone.
+    history: BoardHistory,
 }
 
 /**
@@ -31,6 +47,108 @@ impl BoardManager {
     pub fn new() -> Self {
         Self::default()
     }
+
+    ///
+    /// Rebuilds a BoardManager from a previously generated board, so
+    /// that it's the same as the BoardManager which generated it.
+    /// The letters of the `grid` must agree with the words and
+    /// coordinates of `used`, and every letter must belong to a word.
+    ///
+    /// Example:
+    /// ```
+    /// let boards = read_generated_boards("./boards.json");
+    /// let bm = BoardManager::from_generated_board(&boards[0])?;
+    /// // true
+    /// bm.get_grid_str() == boards[0].grid;
+    /// ```
+    ///
+    pub fn from_generated_board(
+        board: &GeneratedBoard,
+    ) -> Result<Self, BoardError> {
+        let grid: Vec<Vec<char>> = board
+            .grid
+            .split('_')
+            .map(|row| row.chars().collect())
+            .collect();
+        let dimensions = grid.len();
+        if let Some((row, cells)) = grid
+            .iter()
+            .enumerate()
+            .find(|(_, cells)| cells.len() != dimensions)
+        {
+            return Err(BoardError::GridNotSquare {
+                row,
+                length: cells.len(),
+                dimensions,
+            });
+        }
+
+        let mut bm = Self::new();
+        bm.new_list(dimensions);
+
+        // Sorted, so the first error found is always the same one
+        for (word, location) in board.used.iter().sorted_by_key(|(w, _)| *w) {
+            let (row, col) = (location.row, location.col);
+            if word.chars().count() < 2 {
+                return Err(BoardError::WordTooShort {
+                    word: word.to_owned(),
+                    row,
+                    col,
+                });
+            }
+            let end = match location.direction {
+                Direction::H => col + word.chars().count(),
+                Direction::V => row + word.chars().count(),
+            };
+            if row >= dimensions || col >= dimensions || end > dimensions {
+                return Err(BoardError::WordOutOfBounds {
+                    word: word.to_owned(),
+                    row,
+                    col,
+                });
+            }
+
+            for (offset, letter) in word.chars().enumerate() {
+                let (row, col) = match location.direction {
+                    Direction::H => (row, col + offset),
+                    Direction::V => (row + offset, col),
+                };
+                if grid[row][col] != letter {
+                    return Err(BoardError::LetterMismatch {
+                        word: word.to_owned(),
+                        row,
+                        col,
+                        expected: letter,
+                        found: grid[row][col],
+                    });
+                }
+                let cell = bm.grid[bm.cell_index(row, col)];
+                if cell.is_used_in(location.direction) {
+                    return Err(BoardError::OverlappingWords {
+                        word: word.to_owned(),
+                        row,
+                        col,
+                    });
+                }
+            }
+
+            bm.place_word(word, row, col, location.direction);
+        }
+
+        for (row, cells) in grid.iter().enumerate() {
+            for (col, letter) in cells.iter().enumerate() {
+                if *letter != EMPTY_CELL_VALUE && !bm.cell_is_used(row, col) {
+                    return Err(BoardError::UnusedLetter {
+                        row,
+                        col,
+                        letter: *letter,
+                    });
+                }
+            }
+        }
+
+        Ok(bm)
+    }
 }
 
 /**
@@ -43,15 +161,8 @@ impl BoardManager {
     /// attached to the BoardManager
     ///
     pub fn new_list(&mut self, dimensions: usize) {
-        let default_cell = CellContents {
-            letter: EMPTY_CELL_VALUE,
-            is_used_horizontally: false,
-            is_used_vertically: false,
-            is_empty: true,
-        };
-
         self.dimensions = dimensions;
-        self.grid = vec![vec![default_cell; dimensions]; dimensions];
+        self.clear_board();
     }
 
     ///
@@ -102,8 +213,35 @@ impl BoardManager {
     }
 
     ///
-    /// The grid is a 2D Vector of objects and this method
-    /// will squash the entire 2D Vector into a single string,
+    /// Checks if the word has already been placed on the board.
+    ///
+    pub fn is_word_placed(&self, word: &str) -> bool {
+        self.words_placed.contains_key(word)
+    }
+
+    ///
+    /// Returns how many cells of the grid contain a letter.
+    ///
+    pub fn get_number_of_filled_cells(&self) -> usize {
+        self.cells_by_letter.values().map(|cells| cells.len()).sum()
+    }
+
+    ///
+    /// Returns how many cells are used by two words, one
+    /// horizontal and one vertical.
+    ///
+    pub fn get_number_of_crossings(&self) -> usize {
+        self.grid
+            .iter()
+            .filter(|cell| {
+                cell.is_used_horizontally() && cell.is_used_vertically()
+            })
+            .count()
+    }
+
+    ///
+    /// The grid is a (flattened) 2D Vector of cells and this method
+    /// will squash the entire grid into a single string,
     /// extracting only the letters of each CellContents object,
     /// and then separate eaching row-substring with a "_"
     ///
@@ -123,10 +261,10 @@ impl BoardManager {
         // TODO: Need to crop this to shave off unneeded
         // rows and columns
         self.grid
-            .iter()
+            .chunks(self.dimensions.max(1))
             .map(|row| {
                 row.iter()
-                    .map(|cell_contents| cell_contents.letter)
+                    .map(|cell_contents| cell_contents.letter())
                     .join("")
             })
             .join("_")
@@ -135,18 +273,14 @@ impl BoardManager {
     ///
     /// Resets the board (the grid) to it's default state
     /// and "forgets" any words that have been placed.
+    /// The history is forgotten too, so it can't be undone.
     ///
     pub fn clear_board(&mut self) {
         self.words_placed = HashMap::new();
-
-        let default_cell = CellContents {
-            letter: EMPTY_CELL_VALUE,
-            is_used_horizontally: false,
-            is_used_vertically: false,
-            is_empty: true,
-        };
-
-        self.grid = vec![vec![default_cell; self.dimensions]; self.dimensions];
+        self.grid =
+            vec![CellContents::EMPTY; self.dimensions * self.dimensions];
+        self.cells_by_letter = HashMap::new();
+        self.reset_history();
     }
 
     ///
@@ -167,8 +301,8 @@ impl BoardManager {
 
         // Ensure the word fits within the dimensions
         // of the board
-        let overflows_h = start_col + word.len() > self.dimensions;
-        let overflows_v = start_row + word.len() > self.dimensions;
+        let overflows_h = start_col + word_length(word) > self.dimensions;
+        let overflows_v = start_row + word_length(word) > self.dimensions;
         if (direction == Direction::H && overflows_h)
             || (direction == Direction::V && overflows_v)
         {
@@ -176,6 +310,12 @@ impl BoardManager {
             return;
         }
 
+        let cells_before = self.get_cells_of_word(
+            word_length(word),
+            start_row,
+            start_col,
+            direction,
+        );
         let mut cells: Vec<PlacedWordCell> = Vec::new();
 
         for (offset_idx, letter) in word.chars().enumerate() {
@@ -189,9 +329,12 @@ impl BoardManager {
                         letter,
                         direction: Direction::H,
                     });
-                    self.grid[row][col].letter = letter;
-                    self.grid[row][col].is_empty = false;
-                    self.grid[row][col].is_used_horizontally = true;
+                    let cell = self.grid[self.cell_index(row, col)];
+                    self.set_cell(
+                        row,
+                        col,
+                        cell.with_letter(letter, direction),
+                    );
                 }
                 Direction::V => {
                     let row = start_row + offset_idx;
@@ -202,23 +345,26 @@ impl BoardManager {
                         letter,
                         direction: Direction::V,
                     });
-                    self.grid[row][col].letter = letter;
-                    self.grid[row][col].is_empty = false;
-                    self.grid[row][col].is_used_vertically = true;
+                    let cell = self.grid[self.cell_index(row, col)];
+                    self.set_cell(
+                        row,
+                        col,
+                        cell.with_letter(letter, direction),
+                    );
                 }
             }
         }
 
         // This word is now considered a "used word".
         // It's been placed on the grid already.
-        self.words_placed.insert(
-            String::from(word),
-            PlacedWord {
-                cells,
-                direction,
-                word: String::from(word),
-            },
-        );
+        let placed_word = PlacedWord {
+            cells,
+            direction,
+            word: String::from(word),
+        };
+        self.words_placed
+            .insert(String::from(word), placed_word.clone());
+        self.record_operation(word, None, Some(placed_word), cells_before);
     }
 
     ///
@@ -226,30 +372,238 @@ impl BoardManager {
     /// Two objectives:
     ///   1) Remove the word from the `words_placed` hashmap
     ///   2) Maybe remove the letters from the board as long as they
-    ///     aren't also used by another placed word on the board.
+    ///      aren't also used by another placed word on the board.
     ///
     pub fn remove_word_from_board(&mut self, word_to_remove: &str) {
-        if let Some(placed_word) = self.words_placed.get(word_to_remove) {
+        if let Some(placed_word) = self.words_placed.remove(word_to_remove) {
+            let cells_before = self.get_cells_of_word(
+                placed_word.cells.len(),
+                placed_word.cells[0].row,
+                placed_word.cells[0].col,
+                placed_word.direction,
+            );
             for cell in placed_word.cells.iter() {
                 // If this cell is also used in the cross direction,
-                // then it must be being used by two words.
-                if cell.direction == Direction::H {
-                    self.grid[cell.row][cell.col].is_used_horizontally = false;
-                    if !self.grid[cell.row][cell.col].is_used_vertically {
-                        self.grid[cell.row][cell.col].is_empty = true;
-                        self.grid[cell.row][cell.col].letter = EMPTY_CELL_VALUE;
-                    }
-                } else {
-                    self.grid[cell.row][cell.col].is_used_vertically = false;
-                    if !self.grid[cell.row][cell.col].is_used_horizontally {
-                        self.grid[cell.row][cell.col].is_empty = true;
-                        self.grid[cell.row][cell.col].letter = EMPTY_CELL_VALUE;
-                    }
-                }
+                // then it must be being used by two words, and the
+                // letter stays.
+                let contents = self.grid[self.cell_index(cell.row, cell.col)];
+                self.set_cell(
+                    cell.row,
+                    cell.col,
+                    contents.without(cell.direction),
+                );
             }
+            self.record_operation(
+                word_to_remove,
+                Some(placed_word),
+                None,
+                cells_before,
+            );
+        }
+    }
+
+    ///
+    /// Takes a snapshot of the board, which it can later be restored
+    /// to. Snapshots are cheap, they only remember a position in the
+    /// history of the board.
+    ///
+    /// Example:
+    /// ```
+    /// bm.place_word("misuses", 5, 2, Direction::H);
+    /// let snapshot = bm.snapshot();
+    /// bm.place_word("mess", 5, 2, Direction::V);
+    /// bm.restore(snapshot)?;
+    /// // Only "misuses" is on the board
+    /// ```
+    ///
+    pub fn snapshot(&self) -> Snapshot {
+        let applied = self.history.applied;
+        Snapshot {
+            position: applied,
+            last_operation_id: match applied {
+                0 => self.history.reset_id,
+                _ => self.history.operations[applied - 1].id,
+            },
+        }
+    }
+
+    ///
+    /// Restores the board to exactly how it was when the snapshot
+    /// was taken, by undoing (or redoing) the operations since.
+    /// Fails if the board was cleared since, or if the operations
+    /// leading up to the snapshot were undone and replaced.
+    ///
+    pub fn restore(&mut self, snapshot: Snapshot) -> Result<(), BoardError> {
+        let is_in_history = match snapshot.position {
+            0 => snapshot.last_operation_id == self.history.reset_id,
+            position => self
+                .history
+                .operations
+                .get(position - 1)
+                .is_some_and(|op| op.id == snapshot.last_operation_id),
+        };
+        if !is_in_history {
+            return Err(BoardError::UnknownSnapshot);
+        }
+
+        while self.history.applied > snapshot.position {
+            self.undo();
+        }
+        while self.history.applied < snapshot.position {
+            self.redo();
+        }
+        Ok(())
+    }
+
+    ///
+    /// Undoes the last word that was placed (or removed).
+    /// Returns false if there was nothing to undo.
+    ///
+    pub fn undo(&mut self) -> bool {
+        if self.history.applied == 0 {
+            return false;
         }
+        self.history.applied -= 1;
+        let operation = self.history.operations[self.history.applied].clone();
+        self.apply_state(
+            &operation.word,
+            operation.placed_before,
+            operation.cells_before,
+        );
+        true
+    }
+
+    ///
+    /// Redoes the last word placement (or removal) that was undone.
+    /// Returns false if there was nothing to redo.
+    ///
+    pub fn redo(&mut self) -> bool {
+        let Some(operation) =
+            self.history.operations.get(self.history.applied).cloned()
+        else {
+            return false;
+        };
+        self.history.applied += 1;
+        self.apply_state(
+            &operation.word,
+            operation.placed_after,
+            operation.cells_after,
+        );
+        true
+    }
+
+    ///
+    /// Sets the word's entry, and the contents of some cells,
+    /// back to how an operation left (or found) them.
+    ///
+    fn apply_state(
+        &mut self,
+        word: &str,
+        placed_word: Option<PlacedWord>,
+        cells: Vec<(usize, usize, CellContents)>,
+    ) {
+        match placed_word {
+            Some(placed_word) => {
+                self.words_placed.insert(word.to_owned(), placed_word)
+            }
+            None => self.words_placed.remove(word),
+        };
+        for (row, col, contents) in cells {
+            self.set_cell(row, col, contents);
+        }
+    }
+
+    ///
+    /// Adds an operation to the history. Any operations which were
+    /// undone can't be redone anymore.
+    ///
+    fn record_operation(
+        &mut self,
+        word: &str,
+        placed_before: Option<PlacedWord>,
+        placed_after: Option<PlacedWord>,
+        cells_before: Vec<(usize, usize, CellContents)>,
+    ) {
+        let cells_after = cells_before
+            .iter()
+            .map(|(row, col, _)| {
+                (*row, *col, self.grid[self.cell_index(*row, *col)])
+            })
+            .collect();
+
+        self.history.operations.truncate(self.history.applied);
+        self.history.operations.push(BoardOperation {
+            id: self.history.next_id,
+            word: word.to_owned(),
+            placed_before,
+            placed_after,
+            cells_before,
+            cells_after,
+        });
+        self.history.next_id += 1;
+        self.history.applied += 1;
+    }
+
+    ///
+    /// Forgets every operation. Resets have their own id, so
+    /// snapshots from before the reset can't be restored.
+    ///
+    fn reset_history(&mut self) {
+        self.history.operations.clear();
+        self.history.applied = 0;
+        self.history.reset_id = self.history.next_id;
+        self.history.next_id += 1;
+    }
+
+    ///
+    /// The (row, col, contents) of each cell that a word covers.
+    ///
+    fn get_cells_of_word(
+        &self,
+        number_of_letters: usize,
+        start_row: usize,
+        start_col: usize,
+        direction: Direction,
+    ) -> Vec<(usize, usize, CellContents)> {
+        (0..number_of_letters)
+            .map(|offset| match direction {
+                Direction::H => (start_row, start_col + offset),
+                Direction::V => (start_row + offset, start_col),
+            })
+            .map(|(row, col)| (row, col, self.grid[self.cell_index(row, col)]))
+            .collect()
+    }
+
+    ///
+    /// Where a cell is within the (flat) grid.
+    ///
+    fn cell_index(&self, row: usize, col: usize) -> usize {
+        row * self.dimensions + col
+    }
 
-        self.words_placed.remove(word_to_remove);
+    ///
+    /// Changes the contents of a cell, and keeps the
+    /// index of cells by letter up to date.
+    ///
+    fn set_cell(&mut self, row: usize, col: usize, contents: CellContents) {
+        let index = self.cell_index(row, col);
+        let previous = self.grid[index];
+        if !previous.is_empty() {
+            let letter = previous.letter();
+            if let Some(cells) = self.cells_by_letter.get_mut(&letter) {
+                cells.remove(&index);
+                if cells.is_empty() {
+                    self.cells_by_letter.remove(&letter);
+                }
+            }
+        }
+        if !contents.is_empty() {
+            self.cells_by_letter
+                .entry(contents.letter())
+                .or_default()
+                .insert(index);
+        }
+        self.grid[index] = contents;
     }
 
     ///
@@ -289,8 +643,8 @@ impl BoardManager {
     ///
     /// * `word_to_place` - Which word you are trying to place on the board.
     /// * `max_locations_to_find` - Put a limit on the number of locations
-    /// that this will try to find. The lower the limit, the better the
-    /// performance, but the less "randomness" you can achieve.
+    ///   that this will try to find. The lower the limit, the better the
+    ///   performance, but the less "randomness" you can achieve.
     ///
     pub fn find_some_locations_for_word(
         &mut self,
@@ -303,19 +657,36 @@ impl BoardManager {
         // the word we're trying to place. So, if there's an 'e' on the board,
         // and we're trying to play "every", then the 'e' would have two
         // matching indices [0, 2]
-        let all_used_cells: Vec<(PlacedWordCell, Vec<usize>)> = self
-            .words_placed
-            .iter()
-            .flat_map(|p| p.1.cells.clone())
-            .filter_map(|cell| {
-                let indicies = all_indicies_of(word_to_place, cell.letter);
-                if indicies.is_empty() {
-                    None
-                } else {
-                    Some((cell, indicies))
-                }
-            })
-            .collect();
+        // The index of cells by letter means only the matching cells are
+        // ever looked at. A cell which is already used in both directions
+        // can't be crossed again, so it's skipped.
+        let mut all_used_cells: Vec<(usize, usize, Direction, Vec<usize>)> =
+            Vec::new();
+        for letter in word_to_place.chars().unique() {
+            let Some(cell_indexes) = self.cells_by_letter.get(&letter) else {
+                continue;
+            };
+            let indicies = all_indicies_of(word_to_place, letter);
+            for cell_index in cell_indexes.iter() {
+                // The letter on the board is currently used in one direction.
+                // We will try to place the word in the opposite direction.
+                let cell = self.grid[*cell_index];
+                let opposite_direction = match (
+                    cell.is_used_horizontally(),
+                    cell.is_used_vertically(),
+                ) {
+                    (true, false) => Direction::V,
+                    (false, true) => Direction::H,
+                    _ => continue,
+                };
+                all_used_cells.push((
+                    cell_index / self.dimensions,
+                    cell_index % self.dimensions,
+                    opposite_direction,
+                    indicies.clone(),
+                ));
+            }
+        }
 
         // Mix up the order that we evaluate each cell. This helps add randomness to
         // the generated board. If you could place the word 'every' in 6 different locations,
@@ -330,38 +701,33 @@ impl BoardManager {
         // For instance, you can't have two words side-by-side in the same direction,
         // and the final letter has to have an empty cell after it.
         let mut possible_locations: Vec<PossibleWordLocation> = vec![];
-        for (cell, indicies) in shuffled_cells.iter() {
-            // if cell.letter != 'm' {
-            //     continue;
-            // }
+        let number_of_letters = word_length(word_to_place);
+        for (cell_row, cell_col, opposite_direction, indicies) in
+            shuffled_cells.into_iter()
+        {
             // Each of these is an index of the word_to_place where we can lay
             // the word over the letter on the board. If 'e', is the third letter
             // of the word, then we need to start placing it two cells up so that the
             // 'e' will intersect.
             for anchor_idx in indicies.iter() {
-                // The letter on the board is currently used in one direction.
-                // We will try to place the word in the opposite direction.
-                let opposite_direction =
-                    get_opposite_direction(&cell.direction);
-
                 // TODO - Need to handle the usize subtraction better
                 let start_row;
                 let start_col;
                 match opposite_direction {
                     Direction::H => {
-                        if (cell.col as i32 - *anchor_idx as i32) < 0 {
+                        if (cell_col as i32 - *anchor_idx as i32) < 0 {
                             continue;
                         } else {
-                            start_row = cell.row;
-                            start_col = cell.col - anchor_idx
+                            start_row = cell_row;
+                            start_col = cell_col - anchor_idx
                         }
                     }
                     Direction::V => {
-                        if (cell.row as i32 - *anchor_idx as i32) < 0 {
+                        if (cell_row as i32 - *anchor_idx as i32) < 0 {
                             continue;
                         } else {
-                            start_row = cell.row - anchor_idx;
-                            start_col = cell.col
+                            start_row = cell_row - anchor_idx;
+                            start_col = cell_col
                         }
                     }
                 };
@@ -407,7 +773,7 @@ impl BoardManager {
                 for (letter_idx, letter) in word_to_place.chars().enumerate() {
                     // If it's the last letter of the word to place
                     // then there are additional checks we must do.
-                    let is_last_letter = letter_idx == word_to_place.len() - 1;
+                    let is_last_letter = letter_idx == number_of_letters - 1;
 
                     // Determine the indicies of the current letter we're trying to place
                     let (current_row, current_col) = match opposite_direction {
@@ -429,29 +795,22 @@ impl BoardManager {
                     // It might match because it's used in a different word
                     // Or, it could match because it's the match index that we were expecting.
                     let letter_is_already_on_board =
-                        current_cell.letter == letter;
+                        current_cell.letter() == letter;
 
                     //
                     // Is this cell and the next one both occupied?
-                    if letter_is_already_on_board && !is_last_letter  {
+                    if letter_is_already_on_board && !is_last_letter {
                         let passes_mise_mises_check = match opposite_direction {
-                            Direction::H => {
-                                !self.cell_is_used(
-                                    start_row,
-                                    start_col + letter_idx + 1,
-                                )
-                            }
-                            Direction::V => {
-                                !self.cell_is_used(
-                                    start_row + letter_idx + 1,
-                                    start_col,
-                                )
-                            }
+                            Direction::H => !self.cell_is_used(
+                                start_row,
+                                start_col + letter_idx + 1,
+                            ),
+                            Direction::V => !self.cell_is_used(
+                                start_row + letter_idx + 1,
+                                start_col,
+                            ),
                         };
                         if !passes_mise_mises_check {
-                            println!("Encountered an overlap!");
-                            println!("Trying to place: {:#?}", word_to_place);
-                            println!("At {:#?} ({:?},{:?})", current_cell, current_row, current_col);
                             break;
                         }
                     }
@@ -503,7 +862,7 @@ impl BoardManager {
                         && current_col < self.dimensions
                         && passes_last_cell_check
                         && (letter_is_already_on_board
-                            || (current_cell.is_empty
+                            || (current_cell.is_empty()
                                 && adjacent_cells_are_empty));
 
                     //
@@ -512,7 +871,7 @@ impl BoardManager {
                     }
 
                     //
-                    if letter_idx == word_to_place.len() - 1 {
+                    if letter_idx == number_of_letters - 1 {
                         // All cells were valid, this is a valid position
                         // The word can be placed over this letter by starting
                         // at the starting cell and going in the opposite direction.
@@ -541,8 +900,8 @@ impl BoardManager {
         row_idx: usize,
         col_idx: usize,
     ) -> Option<CellContents> {
-        if row_idx < self.grid.len() && col_idx < self.grid[row_idx].len() {
-            Some(self.grid[row_idx][col_idx].clone())
+        if row_idx < self.dimensions && col_idx < self.dimensions {
+            Some(self.grid[self.cell_index(row_idx, col_idx)])
         } else {
             None
         }
@@ -553,21 +912,28 @@ impl BoardManager {
     ///
     pub fn cell_is_used(&self, row_idx: usize, col_idx: usize) -> bool {
         // Make sure row and col are valid indexes
-        if row_idx < self.grid.len() && col_idx < self.grid[row_idx].len() {
-            return !self.grid[row_idx][col_idx].is_empty;
+        if row_idx < self.dimensions && col_idx < self.dimensions {
+            return !self.grid[self.cell_index(row_idx, col_idx)].is_empty();
         }
         false
     }
 
     ///
-    /// Print some debugging information about the BoardManager
-    /// to the console.
+    /// Renders the grid as text, with the row and column numbers
+    /// along the edges. Letters which aren't revealed are drawn
+    /// as a blank (`HIDDEN_CELL_VALUE`), so this can also render
+    /// a board the way the player sees it.
     ///
-    pub fn debug(&self) {
-        let words_used = self.get_used_words();
-
-        println!("---- Board Manager ----");
-
+    /// Example:
+    /// ```
+    /// // Reveal every letter on the board
+    /// let board_string = bm.render_grid(|_row, _col| true);
+    /// ```
+    ///
+    pub fn render_grid(
+        &self,
+        is_revealed: impl Fn(usize, usize) -> bool,
+    ) -> String {
         let mut board_string = String::new();
 
         // Build the column titles
@@ -579,20 +945,37 @@ impl BoardManager {
         board_string.push_str(&format!("{column_titles}\n"));
 
         // Render each row
-        for (row_idx, row) in self.grid.iter().enumerate() {
+        for (row_idx, row) in
+            self.grid.chunks(self.dimensions.max(1)).enumerate()
+        {
             board_string.push_str(&(row_idx % 10).to_string());
             board_string.push(' ');
-            for cell in row.iter() {
-                if cell.letter == EMPTY_CELL_VALUE {
+            for (col_idx, cell) in row.iter().enumerate() {
+                if cell.is_empty() {
                     board_string.push(' ');
+                } else if is_revealed(row_idx, col_idx) {
+                    board_string.push(cell.letter());
                 } else {
-                    board_string.push(cell.letter);
+                    board_string.push(HIDDEN_CELL_VALUE);
                 }
                 board_string.push(' ');
             }
             board_string.push('\n');
         }
-        println!("{board_string}");
+
+        board_string
+    }
+
+    ///
+    /// Print some debugging information about the BoardManager
+    /// to the console.
+    ///
+    pub fn debug(&self) {
+        let words_used = self.get_used_words();
+
+        println!("---- Board Manager ----");
+
+        println!("{}", self.render_grid(|_, _| true));
 
         println!("Dimensions: {0}x{0}", self.dimensions);
 
@@ -602,3 +985,248 @@ impl BoardManager {
         println!("-----------------------");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_used;
+    use proptest::prelude::*;
+
+    ///
+    /// Checks that a word can be placed without disagreeing with
+    /// (or running over) the words already on the board.
+    ///
+    fn can_place_word(
+        bm: &BoardManager,
+        word: &str,
+        row: usize,
+        col: usize,
+        direction: Direction,
+    ) -> bool {
+        let end = match direction {
+            Direction::H => col + word.len(),
+            Direction::V => row + word.len(),
+        };
+        end <= bm.dimensions
+            && !bm.words_placed.contains_key(word)
+            && word.chars().enumerate().all(|(offset, letter)| {
+                let cell = match direction {
+                    Direction::H => bm.get_cell_at(row, col + offset),
+                    Direction::V => bm.get_cell_at(row + offset, col),
+                }
+                .unwrap();
+                cell.is_empty()
+                    || (cell.letter() == letter && !cell.is_used_in(direction))
+            })
+    }
+
+    ///
+    /// Builds the index of cells by letter from scratch,
+    /// to compare with the one the BoardManager keeps.
+    ///
+    fn index_cells_by_letter(
+        bm: &BoardManager,
+    ) -> HashMap<char, BTreeSet<usize>> {
+        let mut cells_by_letter: HashMap<char, BTreeSet<usize>> =
+            HashMap::new();
+        for (index, cell) in bm.grid.iter().enumerate() {
+            if !cell.is_empty() {
+                cells_by_letter
+                    .entry(cell.letter())
+                    .or_default()
+                    .insert(index);
+            }
+        }
+        cells_by_letter
+    }
+
+    proptest! {
+        #[test]
+        fn test_from_generated_board_round_trip(
+            placements in prop::collection::vec(
+                ("[abc]{2,5}", 0..9usize, 0..9usize, any::<bool>()),
+                0..20,
+            )
+        ) {
+            let mut original = BoardManager::new();
+            original.new_list(9);
+            for (word, row, col, is_horizontal) in placements {
+                let direction = match is_horizontal {
+                    true => Direction::H,
+                    false => Direction::V,
+                };
+                if can_place_word(&original, &word, row, col, direction) {
+                    original.place_word(&word, row, col, direction);
+                }
+            }
+
+            let board = GeneratedBoard {
+                grid: original.get_grid_str(),
+                used: original.get_placed_words_for_serialization(),
+                ..GeneratedBoard::default()
+            };
+            let rebuilt = BoardManager::from_generated_board(&board).unwrap();
+
+            prop_assert_eq!(rebuilt.get_grid_str(), original.get_grid_str());
+            prop_assert_eq!(
+                rebuilt.get_placed_words_for_serialization(),
+                original.get_placed_words_for_serialization()
+            );
+            prop_assert_eq!(rebuilt.grid, original.grid);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_restore_is_exact(
+            placements in prop::collection::vec(
+                ("[abc]{2,5}", 0..9usize, 0..9usize, any::<bool>()),
+                0..20,
+            ),
+            snapshot_at in 0..20usize,
+        ) {
+            let mut bm = BoardManager::new();
+            bm.new_list(9);
+            let mut snapshot = (bm.snapshot(), bm.grid.clone(), Vec::new());
+
+            for (index, (word, row, col, is_horizontal)) in
+                placements.into_iter().enumerate()
+            {
+                if index == snapshot_at {
+                    let words = bm.get_used_words().into_iter().sorted();
+                    snapshot = (bm.snapshot(), bm.grid.clone(), words.collect());
+                }
+                let direction = match is_horizontal {
+                    true => Direction::H,
+                    false => Direction::V,
+                };
+                // Every other placed word gets removed again
+                if bm.words_placed.contains_key(&word) {
+                    bm.remove_word_from_board(&word);
+                } else if can_place_word(&bm, &word, row, col, direction) {
+                    bm.place_word(&word, row, col, direction);
+                }
+            }
+
+            let (snapshot, grid, words) = snapshot;
+            bm.restore(snapshot).unwrap();
+            prop_assert_eq!(&bm.grid, &grid);
+            prop_assert_eq!(
+                bm.get_used_words().into_iter().sorted().collect::<Vec<_>>(),
+                words
+            );
+            prop_assert_eq!(&bm.cells_by_letter, &index_cells_by_letter(&bm));
+        }
+    }
+
+    #[test]
+    fn test_snapshot_undo_redo() {
+        let mut bm = BoardManager::new();
+        bm.new_list(9);
+        bm.place_word("misuses", 4, 1, Direction::H);
+        let starting_grid = bm.grid.clone();
+        let snapshot = bm.snapshot();
+
+        bm.place_word("mess", 4, 1, Direction::V);
+        bm.place_word("sums", 7, 0, Direction::H);
+        bm.remove_word_from_board("mess");
+        let final_grid = bm.grid.clone();
+
+        // "misuses" and the "m" it shares with "mess" are still used
+        assert!(bm.undo());
+        let crossing = bm.get_cell_at(4, 1).unwrap();
+        assert!(crossing.is_used_horizontally());
+        assert!(crossing.is_used_vertically());
+        assert_eq!(bm.get_number_of_used_words(), 3);
+
+        bm.restore(snapshot).unwrap();
+        assert_eq!(bm.grid, starting_grid);
+        assert_eq!(bm.get_used_words(), vec!["misuses"]);
+        assert!(bm.undo());
+        assert!(!bm.undo());
+
+        assert!(bm.redo() && bm.redo() && bm.redo() && bm.redo());
+        assert!(!bm.redo());
+        assert_eq!(bm.grid, final_grid);
+
+        // The history after the snapshot is replaced
+        bm.restore(snapshot).unwrap();
+        bm.undo();
+        bm.place_word("emus", 0, 0, Direction::H);
+        assert_eq!(bm.restore(snapshot), Err(BoardError::UnknownSnapshot));
+
+        let snapshot = bm.snapshot();
+        bm.clear_board();
+        assert_eq!(bm.restore(snapshot), Err(BoardError::UnknownSnapshot));
+    }
+
+    #[test]
+    fn test_from_generated_board_errors() {
+        let grid = "mess_e###_s###_s###";
+
+        let mismatch = GeneratedBoard {
+            grid: grid.to_owned(),
+            used: make_used(&[("miss", 0, 0, Direction::H)]),
+            ..GeneratedBoard::default()
+        };
+        assert_eq!(
+            BoardManager::from_generated_board(&mismatch).unwrap_err(),
+            BoardError::LetterMismatch {
+                word: "miss".to_owned(),
+                row: 0,
+                col: 1,
+                expected: 'i',
+                found: 'e',
+            }
+        );
+
+        let out_of_bounds = GeneratedBoard {
+            grid: grid.to_owned(),
+            used: make_used(&[("mess", 0, 1, Direction::H)]),
+            ..GeneratedBoard::default()
+        };
+        assert!(matches!(
+            BoardManager::from_generated_board(&out_of_bounds),
+            Err(BoardError::WordOutOfBounds { .. })
+        ));
+
+        let unused_letter = GeneratedBoard {
+            grid: grid.to_owned(),
+            used: make_used(&[("mess", 0, 0, Direction::H)]),
+            ..GeneratedBoard::default()
+        };
+        assert_eq!(
+            BoardManager::from_generated_board(&unused_letter).unwrap_err(),
+            BoardError::UnusedLetter {
+                row: 1,
+                col: 0,
+                letter: 'e',
+            }
+        );
+
+        for short_word in ["", "m"] {
+            let too_short = GeneratedBoard {
+                grid: grid.to_owned(),
+                used: make_used(&[(short_word, 0, 0, Direction::H)]),
+                ..GeneratedBoard::default()
+            };
+            assert_eq!(
+                BoardManager::from_generated_board(&too_short).unwrap_err(),
+                BoardError::WordTooShort {
+                    word: short_word.to_owned(),
+                    row: 0,
+                    col: 0,
+                }
+            );
+        }
+
+        let not_square = GeneratedBoard {
+            grid: "mess_e###".to_owned(),
+            ..GeneratedBoard::default()
+        };
+        assert!(matches!(
+            BoardManager::from_generated_board(&not_square),
+            Err(BoardError::GridNotSquare { .. })
+        ));
+    }
+}
@@ -1,16 +1,94 @@
+use std::fmt;
+
+use super::board_manager::EMPTY_CELL_VALUE;
+use super::direction::Direction;
+
+/// The lower 21 bits of a cell hold its letter (any `char` fits)
+const LETTER_BITS: u32 = 0x1F_FFFF;
+const USED_HORIZONTALLY: u32 = 1 << 21;
+const USED_VERTICALLY: u32 = 1 << 22;
+const DIRECTION_BITS: u32 = USED_HORIZONTALLY | USED_VERTICALLY;
+
 ///
 /// Each cell of the board (of the grid) will contain
 /// one of these objects. Rather than just store the
 /// letter (char) in the grid, it's helpful (and performant)
 /// to also keep track of how each letter/cell is used.
-/// If we set the flag `is_used_horizontally`: true when
-/// we add a word, it can help us later when we try to remove
-/// the word
+/// If we set the "used horizontally" flag when we add a word,
+/// it can help us later when we try to remove the word.
 ///
-#[derive(Clone, Debug)]
-pub struct CellContents {
-    pub letter: char,
-    pub is_used_horizontally: bool,
-    pub is_used_vertically: bool,
-    pub is_empty: bool,
+/// The letter and the direction flags are packed into a single
+/// `u32`, so the whole grid is one small, flat block of memory,
+/// and reading a cell is just a copy.
+///
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CellContents(u32);
+
+impl CellContents {
+    /// A cell which has no letter in it
+    pub const EMPTY: Self = Self(EMPTY_CELL_VALUE as u32);
+
+    pub fn letter(self) -> char {
+        char::from_u32(self.0 & LETTER_BITS).unwrap_or(EMPTY_CELL_VALUE)
+    }
+
+    pub fn is_used_horizontally(self) -> bool {
+        self.0 & USED_HORIZONTALLY != 0
+    }
+
+    pub fn is_used_vertically(self) -> bool {
+        self.0 & USED_VERTICALLY != 0
+    }
+
+    ///
+    /// Checks if a word in this direction uses the cell.
+    ///
+    pub fn is_used_in(self, direction: Direction) -> bool {
+        self.0 & direction_bit(direction) != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 & DIRECTION_BITS == 0
+    }
+
+    ///
+    /// The cell, after a word in this direction places
+    /// the letter on it.
+    ///
+    pub fn with_letter(self, letter: char, direction: Direction) -> Self {
+        Self(
+            letter as u32
+                | (self.0 & DIRECTION_BITS)
+                | direction_bit(direction),
+        )
+    }
+
+    ///
+    /// The cell, after the word in this direction is removed. The
+    /// letter stays, if a word in the other direction still uses it.
+    ///
+    pub fn without(self, direction: Direction) -> Self {
+        let directions = self.0 & DIRECTION_BITS & !direction_bit(direction);
+        match directions {
+            0 => Self::EMPTY,
+            _ => Self((self.0 & LETTER_BITS) | directions),
+        }
+    }
+}
+
+impl fmt::Debug for CellContents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellContents")
+            .field("letter", &self.letter())
+            .field("is_used_horizontally", &self.is_used_horizontally())
+            .field("is_used_vertically", &self.is_used_vertically())
+            .finish()
+    }
+}
+
+fn direction_bit(direction: Direction) -> u32 {
+    match direction {
+        Direction::H => USED_HORIZONTALLY,
+        Direction::V => USED_VERTICALLY,
+    }
 }
@@ -1,5 +1,7 @@
 use rand::Rng;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::with_rng;
 
 ///
 /// Each word on the board (and each corresponding letter
@@ -7,7 +9,7 @@ use serde::Serialize;
 /// placed horizontally (from left to right), or vertically
 /// (from top to bottom).
 ///
-#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
 pub enum Direction {
     /// Direction::H -> Horizontal
     H,
@@ -39,8 +41,7 @@ pub fn get_opposite_direction(direction: &Direction) -> Direction {
 /// much different.
 ///
 pub fn get_random_direction() -> Direction {
-    let mut rng = rand::thread_rng();
-    if rng.gen::<f64>() > 0.5 {
+    if with_rng(|rng| rng.gen::<f64>()) > 0.5 {
         Direction::H
     } else {
         Direction::V
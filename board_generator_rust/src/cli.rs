@@ -1,11 +1,71 @@
+use std::ops::RangeInclusive;
+
+use chrono::NaiveDate;
 use clap::{Parser, ValueEnum};
 
+use crate::render::SvgView;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
     /// The utility can run in one of many modes.
     #[arg(short, long, value_enum)]
     pub mode: Option<RunMode>,
+    /// For `--mode pattern`. A crossword style pattern, where `?`
+    /// is any letter. Ex: `m?s?s`
+    #[arg(short, long)]
+    pub pattern: Option<String>,
+    /// For `--mode pattern`. Only show words which can be built
+    /// from these letters. For `--mode import`, the wheel of the
+    /// boards. `?` is a wildcard tile. For `--mode word-search`, the
+    /// letters of the hidden words. Ex: `sumeso`
+    #[arg(short, long)]
+    pub letters: Option<String>,
+    /// For `--mode diff`. The old clean dictionary (.txt) or
+    /// precomputed words (.json) file.
+    #[arg(long)]
+    pub old: Option<String>,
+    /// For `--mode diff`. The new clean dictionary (.txt) or
+    /// precomputed words (.json) file.
+    #[arg(long)]
+    pub new: Option<String>,
+    /// A file of generated boards (for diff, play, render, book, pack,
+    /// remix, kriss-kross and codeword modes).
+    /// Defaults to `./boards.json`, except play mode, which defaults to
+    /// generating a new board.
+    #[arg(short, long)]
+    pub boards: Option<String>,
+    /// For `--mode play`, `--mode render` and `--mode remix`. Which board
+    /// of the boards file to use. Play defaults to a random board.
+    #[arg(short, long)]
+    pub index: Option<usize>,
+    /// For `--mode render`, `--mode book`, `--mode kriss-kross` and
+    /// `--mode codeword`. A range of boards to use. Ex: `0-9`
+    #[arg(short, long, value_parser = parse_board_range)]
+    pub range: Option<RangeInclusive<usize>>,
+    /// For `--mode render`. Which view of the board to draw.
+    /// Defaults to every view.
+    #[arg(long, value_enum)]
+    pub view: Option<SvgView>,
+    /// For `--mode daily`. The (first) day of the daily puzzle,
+    /// as `YYYY-MM-DD`. Defaults to today.
+    #[arg(long)]
+    pub date: Option<NaiveDate>,
+    /// For `--mode daily`. How many days of puzzles to generate,
+    /// starting from `--date`. Defaults to 1.
+    #[arg(long)]
+    pub days: Option<usize>,
+    /// For `--mode import`. A text file of hand-drawn boards, drawn
+    /// as rows of letters and `.`, separated by blank lines.
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// For `--mode generate`. How many boards to generate.
+    /// Defaults to `number_of_boards` in the config.
+    #[arg(long)]
+    pub number_of_boards: Option<usize>,
+    /// For `--mode generate`. Don't print each board as it's generated.
+    #[arg(long)]
+    pub quiet: bool,
 }
 
 #[derive(
@@ -16,4 +76,53 @@ pub enum RunMode {
     Files,
     /// Generate boards. Use the pre-computed word file. Extremely fast for large numbers of boards.
     Generate,
+    /// Generate (bigger) weekend boards, which start from two crossing starting words. Their wheel can have 8 or 9 letters.
+    Weekend,
+    /// Find every word in the clean dictionary matching a pattern. Use with `--pattern` and (optionally) `--letters`.
+    Pattern,
+    /// Report on the clean dictionary and precomputed dictionary files. Flags suspicious words, and starting words which can't build enough words.
+    Audit,
+    /// Compare two builds of the dictionary, and list the boards affected by the changes. Use with `--old`, `--new` and (optionally) `--boards`.
+    Diff,
+    /// Play a board in the terminal. Plays a board from `--boards` (optionally `--index`), or generates a new one.
+    Play,
+    /// Draw boards as SVG images. Use with `--index` or `--range`, and (optionally) `--boards` and `--view`.
+    Render,
+    /// Export boards as a printable puzzle booklet (HTML). Use with (optionally) `--boards` and `--range`.
+    Book,
+    /// Order boards into a campaign of chapters and levels, and write its manifest. Use with (optionally) `--boards`.
+    Pack,
+    /// Generate the daily puzzle(s), which are the same for every player. Needs the `DAILY_PUZZLE_SALT` variable. Use with (optionally) `--date` and `--days`.
+    Daily,
+    /// Turn hand-drawn (ASCII) boards into generated boards. Use with `--file` and (optionally) `--letters`.
+    Import,
+    /// Derive variants of a board, which swap some of its words for others. Use with `--index` and (optionally) `--boards`.
+    Remix,
+    /// Generate word search puzzles. Use with (optionally) `--letters`, otherwise each puzzle uses a random 7-letter word.
+    WordSearch,
+    /// Export boards as kriss-kross (fill-in) puzzles. Boards whose fill isn't unique are rejected. Use with (optionally) `--boards` and `--range`.
+    KrissKross,
+    /// Turn boards into codeword puzzles, where every letter is a number. Use with (optionally) `--boards` and `--range`.
+    Codeword,
+}
+
+///
+/// Parses a range of board indexes. Both ends are included.
+///
+/// Example: `"0-9"` -> `0..=9`, `"4"` -> `4..=4`
+///
+fn parse_board_range(range: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse_index = |index: &str| {
+        index
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{index}' is not a board index"))
+    };
+    match range.split_once('-') {
+        Some((start, end)) => Ok(parse_index(start)?..=parse_index(end)?),
+        None => {
+            let index = parse_index(range)?;
+            Ok(index..=index)
+        }
+    }
 }
@@ -1,15 +1,49 @@
+use chrono::NaiveDate;
+
+use crate::analysis::AmbiguityAction;
+use crate::dictionary::{Language, NormalizationPolicy};
+use crate::puzzles::WordSearchOverlap;
+
 #[derive(Clone, Debug)]
 pub struct AppConfig {
     /// After generating the boards, should the boards be
     /// written to an output file. If So, which file.
     pub should_output_to_file: bool,
     pub output_filepath: String,
+    /// Where the JSON version of the dictionary audit is written
+    pub audit_report_filepath: String,
+    /// Where the JSON version of the dictionary diff is written
+    pub diff_report_filepath: String,
+    /// Where the boards imported from hand-drawn (ASCII) boards are written
+    pub imported_boards_filepath: String,
     /// Paths to the different dictionary files to read in
     pub base_dictionary_filepath: String,
     pub clean_dictionary_filepath: String,
     pub precomputed_words_filepath: String,
+    /// The settings the precomputed words file was built with. When they
+    /// change, the precomputed words file is rebuilt from scratch.
+    pub precomputed_settings_filepath: String,
+    /// The "sorted unique letters" index of the clean dictionary. This is
+    /// the same format the Go dictionary generator and word solver use.
+    pub sorted_uniques_filepath: String,
+    /// Directory of the (optional) theme files. Each `<theme>.txt` file
+    /// lists the words which belong to that theme.
+    pub themes_directory: String,
     /// Which "bad words dictionary" file to read in.
     pub bad_words_filepath: String,
+    /// Words that look offensive to the profanity filter, but
+    /// aren't. Ex: "glasses" contains "asses".
+    pub profanity_allowlist_filepath: String,
+    /// Bad words with at least this many letters are also searched
+    /// for within other words. Shorter bad words ("ass") are
+    /// found inside of too many innocent words ("class").
+    pub min_profanity_substring_length: usize,
+    /// The language of the dictionary files. Only english is built into
+    /// the binary, other languages are read from `precomputed_words_filepath`.
+    pub language: Language,
+    /// How words from the base dictionary are normalized. Ex: Should
+    /// accents be stripped from letters
+    pub normalization: NormalizationPolicy,
     /// How many boards should be generated.
     pub number_of_boards: usize,
     /// After running, should the app console.log out how long
@@ -48,12 +82,191 @@ pub struct GeneratorConfig {
     /// For each board that is generated, should information
     /// about that board be console.logged.
     pub should_log_board_info: bool,
+    /// Generate themed boards (Ex: "animals"). The theme must match
+    /// the name of a file within the themes directory.
+    pub theme: Option<String>,
+    /// For themed boards, the minimum number of themed
+    /// words that must be placed on each board.
+    pub min_themed_words: usize,
+    /// A slot is ambiguous when more than one word fits it, given the
+    /// letters of the words crossing it. This is how many ambiguous
+    /// slots a board is allowed to have.
+    pub max_ambiguous_slots: usize,
+    /// What to do with boards that have more than `max_ambiguous_slots`.
+    /// With `Ignore`, the slots aren't looked for at all.
+    pub ambiguity_action: AmbiguityAction,
+    /// How many of the wheel's letters are replaced with a wildcard
+    /// ("joker") tile, which the player can use as any letter.
+    pub wildcard_tiles: usize,
+    /// Once a board has enough words, try to fit more of the possible
+    /// words into its gaps. See `densify_board()`.
+    pub should_densify_boards: bool,
+    /// The densification pass stops once the board has this many words.
+    /// This is separate from `max_words_to_place`.
+    pub max_words_after_densifying: usize,
+}
+
+///
+/// How boards are drawn by the SVG renderer. Colors can be
+/// any SVG/CSS color. Ex: "#f6f6f6" or "white"
+///
+#[derive(Clone, Debug)]
+pub struct RenderConfig {
+    /// Where the rendered boards are written
+    pub output_directory: String,
+    /// The width and height (in pixels) of each cell of the grid
+    pub cell_size: usize,
+    pub font_family: String,
+    pub background_color: String,
+    pub cell_color: String,
+    pub line_color: String,
+    pub letter_color: String,
+    /// Cells where two words cross (annotated view)
+    pub intersection_color: String,
+    /// The numbers of each word's first cell (annotated view)
+    pub number_color: String,
+    pub wheel_color: String,
+    pub wheel_letter_color: String,
+}
+
+///
+/// Settings for exporting boards into other formats.
+///
+#[derive(Clone, Debug)]
+pub struct ExportConfig {
+    /// Where the printable puzzle booklet (HTML) is written
+    pub puzzle_book_filepath: String,
+    pub book_title: String,
+    /// How many puzzles are printed on each page of the booklet
+    pub boards_per_page: usize,
+    /// Where the kriss-kross (fill-in) puzzles are written
+    pub kriss_kross_filepath: String,
+}
+
+///
+/// Config for the daily puzzles (`--mode daily`)
+///
+#[derive(Clone, Debug)]
+pub struct DailyConfig {
+    /// Where the calendar (date -> board) of daily puzzles is written
+    pub calendar_filepath: String,
+    /// Mixed into every day's seed, so the puzzles can't be
+    /// predicted without it. Changing it changes every puzzle.
+    /// Read from `DAILY_PUZZLE_SALT`, and no daily puzzles are
+    /// generated without it.
+    pub salt: Option<String>,
+    /// The first day that has a daily puzzle. Changing it (or the
+    /// repeat window) changes the puzzles of every day after it.
+    pub epoch: NaiveDate,
+    /// The same starting word won't be used again within this many days
+    pub repeat_window: usize,
+    /// How many (seeded) attempts at a day's board before giving up
+    pub max_attempts_per_day: usize,
+}
+
+///
+/// Config for ordering boards into a campaign (`--mode pack`)
+///
+#[derive(Clone, Debug)]
+pub struct PackConfig {
+    /// Where the pack's manifest (JSON) is written
+    pub pack_filepath: String,
+    pub levels_per_chapter: usize,
+    /// Every Nth level is a themed "boss" board. 0 means no bosses.
+    pub boss_every: usize,
+    /// The same starting word won't be used again within this many levels
+    pub repeat_window: usize,
+    /// How much difficulty each letter of the wheel is worth when
+    /// ordering the levels. Higher values keep the wheel sizes in
+    /// order, and 0 orders the levels by difficulty alone.
+    pub wheel_size_weight: f32,
+}
+
+///
+/// Config for deriving variants of a board (`--mode remix`)
+///
+#[derive(Clone, Debug)]
+pub struct RemixConfig {
+    /// Where the variants (JSON) are written
+    pub remix_filepath: String,
+    pub variants_per_board: usize,
+    /// How many leaf words (words which cross only one other word)
+    /// are swapped out of the board for each variant
+    pub leaf_words_to_remove: usize,
+    /// Variants may be transposed (mirrored along the main diagonal)
+    pub allow_transposing: bool,
+    /// How many attempts at a variant before giving up
+    pub max_attempts: usize,
+}
+
+///
+/// Config for word search puzzles (`--mode word-search`)
+///
+#[derive(Clone, Debug)]
+pub struct WordSearchConfig {
+    /// Where the puzzles (JSON) are written
+    pub puzzles_filepath: String,
+    pub number_of_puzzles: usize,
+    /// The width and height of the (square) grid
+    pub grid_size: usize,
+    /// Puzzles with fewer hidden words are thrown out
+    pub min_words: usize,
+    pub max_words: usize,
+    /// Words can read right to left, and bottom to top
+    pub allow_reversed: bool,
+    pub allow_diagonal: bool,
+    pub overlap: WordSearchOverlap,
+    /// How many random positions are tried for each word
+    pub max_attempts_per_word: usize,
+    /// How many puzzles can fail before giving up
+    pub max_failed_attempts: usize,
+}
+
+///
+/// Config for codeword puzzles (`--mode codeword`)
+///
+#[derive(Clone, Debug)]
+pub struct CodewordConfig {
+    /// Where the puzzles (JSON) are written
+    pub puzzles_filepath: String,
+    /// Boards which need more starters than this are thrown out
+    pub max_starters: usize,
+}
+
+///
+/// Config for weekend boards (`--mode weekend`), which start from two
+/// crossing long words instead of one. See `try_to_generate_a_weekend_board()`.
+///
+#[derive(Clone, Debug)]
+pub struct WeekendConfig {
+    /// Where the weekend boards (JSON) are written
+    pub boards_filepath: String,
+    pub number_of_boards: usize,
+    /// Two starting words are only paired if a wheel of this many
+    /// letters can build both of them
+    pub max_wheel_letters: usize,
+    /// Weekend boards are bigger, so these replace the
+    /// generator's settings of the same name
+    pub board_dimensions: usize,
+    pub max_words_to_place: usize,
+    pub max_words_to_consider: usize,
+    pub max_words_after_densifying: usize,
+    /// How many boards can fail before giving up
+    pub max_failed_attempts: usize,
 }
 
 #[derive(Clone, Debug)]
 pub struct Config {
     pub app: AppConfig,
     pub generator: GeneratorConfig,
+    pub render: RenderConfig,
+    pub export: ExportConfig,
+    pub pack: PackConfig,
+    pub daily: DailyConfig,
+    pub remix: RemixConfig,
+    pub word_search: WordSearchConfig,
+    pub codeword: CodewordConfig,
+    pub weekend: WeekendConfig,
 }
 
 impl Default for Config {
@@ -62,6 +275,11 @@ impl Default for Config {
             app: AppConfig {
                 should_output_to_file: true,
                 output_filepath: String::from("./boards.json"),
+                audit_report_filepath: String::from("./dictionary_audit.json"),
+                diff_report_filepath: String::from("./dictionary_diff.json"),
+                imported_boards_filepath: String::from(
+                    "./imported_boards.json",
+                ),
                 base_dictionary_filepath: String::from(
                     "./dictionary_files/84000_gwicks.txt",
                 ),
@@ -71,9 +289,22 @@ impl Default for Config {
                 bad_words_filepath: String::from(
                     "./dictionary_files/bad_words_to_exclude.txt",
                 ),
+                profanity_allowlist_filepath: String::from(
+                    "./dictionary_files/profanity_allowlist.txt",
+                ),
+                min_profanity_substring_length: 5,
+                language: Language::English,
+                normalization: NormalizationPolicy { fold_accents: true },
                 precomputed_words_filepath: String::from(
                     "./dictionary_files/precomputed_words.json",
                 ),
+                precomputed_settings_filepath: String::from(
+                    "./dictionary_files/precomputed_settings.json",
+                ),
+                sorted_uniques_filepath: String::from(
+                    "./dictionary_files/sorted_uniques.json",
+                ),
+                themes_directory: String::from("./dictionary_files/themes"),
                 // How many boards to generate
                 // The precomputed algorithm becomes incomparably faster shortly after that.
                 // Technically the pre-computed IS faster per board always, but requires a longer
@@ -97,6 +328,86 @@ impl Default for Config {
                 // Don't log board info if you have a bunch of boards
                 // should_log_board_info: false,
                 should_log_board_info: true,
+                theme: None,
+                min_themed_words: 3,
+                // Most boards have a few ambiguous slots, so the check
+                // is off unless it's asked for
+                max_ambiguous_slots: 10,
+                ambiguity_action: AmbiguityAction::Ignore,
+                wildcard_tiles: 0,
+                should_densify_boards: false,
+                max_words_after_densifying: 26,
+            },
+            // The colors match the game app's theme
+            render: RenderConfig {
+                output_directory: String::from("./renders"),
+                cell_size: 48,
+                font_family: String::from("Arial, sans-serif"),
+                background_color: String::from("#2f2f2f"),
+                cell_color: String::from("#f6f6f6"),
+                line_color: String::from("#0f0f0f"),
+                letter_color: String::from("#0f0f0f"),
+                intersection_color: String::from("#87ebbc"),
+                number_color: String::from("#396cd8"),
+                wheel_color: String::from("#f3dfca"),
+                wheel_letter_color: String::from("#2f2f2f"),
+            },
+            export: ExportConfig {
+                puzzle_book_filepath: String::from(
+                    "./renders/puzzle_book.html",
+                ),
+                book_title: String::from("Word Puzzles"),
+                boards_per_page: 2,
+                kriss_kross_filepath: String::from("./kriss_kross.json"),
+            },
+            pack: PackConfig {
+                pack_filepath: String::from("./pack.json"),
+                levels_per_chapter: 20,
+                boss_every: 10,
+                repeat_window: 30,
+                wheel_size_weight: 5.0,
+            },
+            daily: DailyConfig {
+                calendar_filepath: String::from("./daily_puzzles.json"),
+                // The real salt is kept out of the repo
+                salt: std::env::var("DAILY_PUZZLE_SALT").ok(),
+                epoch: NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .expect("The epoch is a valid date."),
+                repeat_window: 365,
+                max_attempts_per_day: 200,
+            },
+            remix: RemixConfig {
+                remix_filepath: String::from("./remixes.json"),
+                variants_per_board: 5,
+                leaf_words_to_remove: 3,
+                allow_transposing: true,
+                max_attempts: 50,
+            },
+            word_search: WordSearchConfig {
+                puzzles_filepath: String::from("./word_search.json"),
+                number_of_puzzles: 1,
+                grid_size: 10,
+                min_words: 8,
+                max_words: 12,
+                allow_reversed: true,
+                allow_diagonal: true,
+                overlap: WordSearchOverlap::SharedLetters,
+                max_attempts_per_word: 100,
+                max_failed_attempts: 20,
+            },
+            codeword: CodewordConfig {
+                puzzles_filepath: String::from("./codewords.json"),
+                max_starters: 3,
+            },
+            weekend: WeekendConfig {
+                boards_filepath: String::from("./weekend_boards.json"),
+                number_of_boards: 1,
+                max_wheel_letters: 9,
+                board_dimensions: 15,
+                max_words_to_place: 28,
+                max_words_to_consider: 60,
+                max_words_after_densifying: 36,
+                max_failed_attempts: 200,
             },
         }
     }
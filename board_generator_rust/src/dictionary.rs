@@ -1,7 +1,16 @@
 mod dictionary_manager;
 mod dictionary_utils;
+mod language;
+mod profanity_filter;
+mod wildcard_match;
 
 pub use dictionary_manager::*;
 pub use dictionary_utils::{
-    create_all_dictionary_files, read_clean_dictionary,
+    combine_sorted_letters, create_all_dictionary_files, read_clean_dictionary,
+    read_precomputed_file, read_theme_files, sorted_letters_missing_from,
 };
+pub use language::{
+    is_valid_word, normalize_word, Language, NormalizationPolicy,
+};
+pub use profanity_filter::ProfanityFilter;
+pub use wildcard_match::*;
@@ -1,11 +1,21 @@
 use super::dictionary_utils::*;
-use crate::utils::get_random_int_in_range;
+use super::{Language, ProfanityFilter, WildcardMatch, WILDCARD_LETTER};
+use crate::generator::WordLocationsOnBoard;
+use crate::utils::{get_random_int_in_range, with_rng, word_length};
 use itertools::Itertools;
 use rand::seq::SliceRandom;
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+};
+
+/// Above this many unique letters, it's faster to check every key of
+/// the sorted uniques index than every combination of the letters.
+const MAX_LETTERS_FOR_COMBINATIONS: usize = 12;
 
 // Include the precomputed dictionary file as part of the binary
-const PRECOMPUTED_JSON: &'static str = include_str!("../../dictionary_files/precomputed_words.json");
+const PRECOMPUTED_JSON: &str =
+    include_str!("../../dictionary_files/precomputed_words.json");
 
 ///
 /// The DictionaryManager is a utility class used to do things like
@@ -25,6 +35,18 @@ pub struct DictionaryManager {
     to_sorted: HashMap<String, Vec<char>>,
     subwords_map: HashMap<String, Vec<String>>,
     subwords_keys: Vec<String>,
+    /// The "sorted unique letters" index used to look up words
+    sorted_uniques: SortedUniquesMap,
+    /// Maps (word length, letter position, letter) to the indexes (within
+    /// `all_words`) of the words which have that letter at that position.
+    by_position: HashMap<(usize, usize, char), Vec<usize>>,
+    profanity_filter: ProfanityFilter,
+    language: Language,
+    /// Maps the name of a theme ("animals") to the words in that theme
+    themes: HashMap<String, HashSet<String>>,
+    /// Maps the name of a theme to the starting words which can build
+    /// its words, and how many of its words each of them can build
+    theme_starting_words: HashMap<String, Vec<(String, usize)>>,
 }
 
 // ===============Static Methods=================
@@ -46,7 +68,14 @@ impl DictionaryManager {
     ///
     pub fn load_clean_dictionary(&mut self, dictionary_filepath: &str) {
         // Load the dictionary file into a Vector of words
-        let all_words = read_clean_dictionary(dictionary_filepath);
+        self.load_words(read_clean_dictionary(dictionary_filepath));
+    }
+
+    ///
+    /// Same as `load_clean_dictionary()`, but the (clean) words
+    /// are given directly instead of being read from a file.
+    ///
+    pub fn load_words(&mut self, all_words: Vec<String>) {
         self.all_words = all_words.clone();
 
         // Group the words by character length in a HashMap where the keys
@@ -55,7 +84,7 @@ impl DictionaryManager {
         let mut by_length = HashMap::new();
         for word in all_words.iter() {
             by_length
-                .entry(word.len())
+                .entry(word_length(word))
                 .or_insert_with(Vec::new)
                 .push(word.to_owned());
         }
@@ -68,6 +97,57 @@ impl DictionaryManager {
             to_sorted.insert(word.to_owned(), word.chars().sorted().collect());
         }
         self.to_sorted = to_sorted;
+
+        self.sorted_uniques = create_sorted_uniques_map(&all_words);
+
+        // Index every letter of every word by its position, for
+        // crossword style pattern lookups. Ex: "m?s?s"
+        let mut by_position = HashMap::new();
+        for (word_index, word) in all_words.iter().enumerate() {
+            let number_of_letters = word_length(word);
+            for (position, letter) in word.chars().enumerate() {
+                by_position
+                    .entry((number_of_letters, position, letter))
+                    .or_insert_with(Vec::new)
+                    .push(word_index);
+            }
+        }
+        self.by_position = by_position;
+    }
+
+    ///
+    /// Loads a "sorted unique letters" file (the format created by the
+    /// Go dictionary generator) instead of a clean dictionary file.
+    /// Words which are too short or too long for the game are skipped.
+    ///
+    pub fn load_sorted_uniques_file(&mut self, sorted_uniques_filepath: &str) {
+        let sorted_uniques_map =
+            read_sorted_uniques_file(sorted_uniques_filepath)
+                .expect("Could not read the sorted uniques file.");
+
+        let mut all_words: Vec<String> = sorted_uniques_map
+            .into_values()
+            .flatten()
+            .map(|(_, word)| word)
+            .filter(|word| {
+                (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&word_length(word))
+            })
+            .collect();
+        all_words.sort();
+        all_words.dedup();
+
+        self.load_words(all_words);
+    }
+
+    ///
+    /// Writes the words of the dictionary in the "sorted unique letters"
+    /// format, which can be read by the word solver app.
+    ///
+    pub fn write_sorted_uniques_file(&self, sorted_uniques_filepath: &str) {
+        write_sorted_uniques_file(
+            sorted_uniques_filepath,
+            &self.sorted_uniques,
+        );
     }
 
     ///
@@ -75,26 +155,235 @@ impl DictionaryManager {
     /// seven-letter words, and maps each of them to all of the words
     /// it's letters can create.
     ///
+    /// The (english) precomputed file is built into the binary.
+    ///
     pub fn load_precomputed_dictionary(&mut self) {
-        if let Ok(precomputed_words) = serde_json::from_str::<HashMap<String, Vec<String>>>(PRECOMPUTED_JSON) {
+        self.parse_precomputed_dictionary(PRECOMPUTED_JSON);
+    }
+
+    ///
+    /// Same as `load_precomputed_dictionary()`, but reads the precomputed
+    /// file from disk. This is how dictionaries of other languages
+    /// are loaded.
+    ///
+    pub fn load_precomputed_dictionary_file(&mut self, filepath: &str) {
+        let precomputed_json = fs::read_to_string(filepath)
+            .expect("Could not read the precomputed word-map file.");
+        self.parse_precomputed_dictionary(&precomputed_json);
+    }
+
+    fn parse_precomputed_dictionary(&mut self, precomputed_json: &str) {
+        if let Ok(precomputed_words) = serde_json::from_str::<
+            HashMap<String, Vec<String>>,
+        >(precomputed_json)
+        {
             // 5MB ram
+            // Sorted, so that a seeded random choice is repeatable
             let keys: Vec<String> = precomputed_words
                 .keys()
                 .map(|word| word.to_owned())
+                .sorted()
                 .collect();
             self.subwords_keys = keys;
             // 15MB ram
             self.subwords_map = precomputed_words;
+            self.index_theme_starting_words();
         }
     }
 
+    ///
+    /// Sets which language the words of this dictionary are in.
+    ///
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    pub fn get_language(&self) -> Language {
+        self.language
+    }
+
+    ///
+    /// Gives the DictionaryManager a profanity filter, which is used to
+    /// check finished boards for offensive words. Without one, no
+    /// words are considered offensive.
+    ///
+    pub fn load_profanity_filter(&mut self, profanity_filter: ProfanityFilter) {
+        self.profanity_filter = profanity_filter;
+    }
+
+    ///
+    /// Scans the rows and columns of a serialized grid for offensive
+    /// words, including short ones which span two placed words (`used`).
+    /// Returns every offending run of letters.
+    ///
+    pub fn find_profanity_in_grid(
+        &self,
+        grid: &str,
+        used: &WordLocationsOnBoard,
+    ) -> Vec<String> {
+        self.profanity_filter.find_profanity_in_grid(grid, used)
+    }
+
+    ///
+    /// Checks if a run of letters is offensive, according
+    /// to the profanity filter.
+    ///
+    pub fn is_profane(&self, word: &str) -> bool {
+        self.profanity_filter.is_profane(word)
+    }
+
     pub fn get_random_starting_word(&self) -> Option<(&String, &Vec<String>)> {
-        // let random_word = self.subwords_keys.choose(&mut rand::thread_rng())?;
+        let random_word = with_rng(|rng| self.subwords_keys.choose(rng))?;
+
+        self.subwords_map.get_key_value(random_word)
+    }
+
+    ///
+    /// Checks if the word is in the (clean) dictionary.
+    ///
+    pub fn is_word(&self, word: &str) -> bool {
+        self.to_sorted.contains_key(word)
+    }
+
+    ///
+    /// Every starting word of the precomputed dictionary, sorted.
+    ///
+    pub fn get_starting_words(&self) -> &[String] {
+        &self.subwords_keys
+    }
+
+    ///
+    /// Looks up a starting word, and all of the words its
+    /// letters can build.
+    ///
+    pub fn get_starting_word(
+        &self,
+        starting_word: &str,
+    ) -> Option<(&String, &Vec<String>)> {
+        self.subwords_map.get_key_value(starting_word)
+    }
+
+    ///
+    /// Loads the optional theme (tag) files, which map words to themes.
+    /// See `read_theme_files()` for the format.
+    ///
+    pub fn load_theme_files(&mut self, themes_directory: &str) {
+        self.themes = read_theme_files(themes_directory);
+        self.index_theme_starting_words();
+    }
+
+    ///
+    /// Finds the starting words which can build at least one word of
+    /// each theme, so that picking the starting word of a themed board
+    /// doesn't have to check the subwords of every starting word.
+    ///
+    fn index_theme_starting_words(&mut self) {
+        self.theme_starting_words = self
+            .themes
+            .keys()
+            .map(|theme| {
+                let starting_words = self
+                    .subwords_keys
+                    .iter()
+                    .filter_map(|starting_word| {
+                        let subwords = self.subwords_map.get(starting_word)?;
+                        let themed_count =
+                            self.get_themed_words(theme, subwords).len();
+                        (themed_count > 0)
+                            .then(|| (starting_word.to_owned(), themed_count))
+                    })
+                    .collect();
+                (theme.to_owned(), starting_words)
+            })
+            .collect();
+    }
+
+    ///
+    /// Returns only the words which belong to the theme.
+    ///
+    pub fn get_themed_words(
+        &self,
+        theme: &str,
+        words: &[String],
+    ) -> Vec<String> {
+        match self.themes.get(theme) {
+            Some(theme_words) => words
+                .iter()
+                .filter(|word| theme_words.contains(*word))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    ///
+    /// Randomly selects a starting word whose letters can build at least
+    /// `min_themed_words` words from the theme. Starting words which can
+    /// build more themed words are more likely to be chosen.
+    ///
+    pub fn get_random_starting_word_for_theme(
+        &self,
+        theme: &str,
+        min_themed_words: usize,
+    ) -> Option<(&String, &Vec<String>)> {
+        let candidates: Vec<&(String, usize)> = self
+            .theme_starting_words
+            .get(theme)?
+            .iter()
+            .filter(|(_, themed_count)| *themed_count >= min_themed_words)
+            .collect();
 
-        // self.subwords_map.get_key_value(random_word)
-        let random_word = "misuses".to_owned();
+        let (random_word, _) = with_rng(|rng| {
+            candidates.choose_weighted(rng, |(_, count)| *count).ok()
+        })?;
 
-        self.subwords_map.get_key_value(&random_word)
+        self.subwords_map.get_key_value(random_word)
+    }
+
+    ///
+    /// Finds the starting words which could be paired with `anchor` on
+    /// a multi-anchor board. Both words must be buildable from a single
+    /// wheel of at most `max_wheel_letters` letters, which also means
+    /// they share enough letters to cross each other.
+    ///
+    /// Example:
+    /// ```
+    /// // ["abdomen", "adenoma", "admired", ...]
+    /// dictionary.get_anchor_partners("denmark", 9);
+    /// ```
+    ///
+    pub fn get_anchor_partners(
+        &self,
+        anchor: &str,
+        max_wheel_letters: usize,
+    ) -> Vec<&String> {
+        let anchor_letters: Vec<char> = anchor.chars().sorted().collect();
+        self.subwords_keys
+            .iter()
+            .filter(|partner| *partner != anchor)
+            .filter(|partner| {
+                let partner_letters: Vec<char> =
+                    partner.chars().sorted().collect();
+                combine_sorted_letters(&anchor_letters, &partner_letters).len()
+                    <= max_wheel_letters
+            })
+            .collect()
+    }
+
+    ///
+    /// Randomly selects two starting words (anchors) for a multi-anchor
+    /// board. See `get_anchor_partners()`. Returns `None` when the
+    /// first (random) anchor has no partners.
+    ///
+    pub fn get_random_anchor_pair(
+        &self,
+        max_wheel_letters: usize,
+    ) -> Option<(&String, &String)> {
+        let (anchor, _) = self.get_random_starting_word()?;
+        let partners = self.get_anchor_partners(anchor, max_wheel_letters);
+        let partner = with_rng(|rng| partners.choose(rng).copied())?;
+
+        Some((anchor, partner))
     }
 
     ///
@@ -124,104 +413,184 @@ impl DictionaryManager {
     ///
     /// Given some letters, this utility will search the entire dictionary
     /// for all words that can be constructed using either some or all of
-    /// the given letters.
-    ///
-    /// To help reduce MEMORY complexity, the searching algorithm
-    /// will use pointers and loops rather than letter counters.
-    ///
-    /// Here is an explanation of the algorithm used to find words that can
-    /// be built using some or all of the given letters-set:
-    ///
-    /// 1) First, all dictionary words must have their characters sorted the same
-    ///     way as the given letters-set. The dictionary words are pre-sorted
-    ///     during app initialization. Ex: `nathan` -> `aahnnt`
-    /// 2) For each word in the dictionary (dictionary word), it will be compared to the given
-    ///     letters of the "find-word" in the following way:
-    /// 3) There is a pointer to the first letter of each word.
-    /// 4) We compare each letter from the find word, to the dictionary word. If two letters
-    ///     match, then we advance both pointers. If they don't match, we only
-    ///     advance the "find-word" pointer to the next letter of the letters-set.
-    ///     1) If we get through all letters of the dictionary-word, then that word is a match.
-    ///     2) If we get through all letters of the find-word, then the dictionary
-    ///     word cannot be built from the find-word.
+    /// the given letters. The words are returned in alphabetical order.
+    ///
+    /// The lookup uses the "sorted unique letters" index, the same way
+    /// the word solver app does:
+    ///
+    /// 1) The given letters are sorted, and reduced to their unique letters.
+    ///    Ex: `nathan` -> sorted: `aahnnt`, unique: `ahnt`
+    /// 2) Every word that can be built from the letters must have unique
+    ///    letters which are some combination of the given unique letters.
+    ///    Ex: `a`, `h`, `ah`, `an`, ... `ahnt`
+    /// 3) Each combination is a key into the index. The words under that key
+    ///    still need to be checked, since a word might use a letter more
+    ///    times than it was given. Ex: `ant` fits, but `taunt` does not.
     ///
     pub fn find_all_words_for_letters(
         &self,
         given_letters_set: &str,
         can_be_looked_up: bool,
     ) -> Vec<String> {
-        // The algorithm I used for finding words that can be built
-        // from a set of letters requires the set of letters to be sorted
-        // the same way as the dictionary words.
-        let letters_to_find: Vec<char> = if can_be_looked_up {
-            // If the letters are in the form of a known word,
-            // that has been presorted. You know it can be looked up
-            // if you originally got the word by calling
-            // self.get_random_word_by_length()
-            self.to_sorted[given_letters_set].clone()
-        } else {
-            // If the letters are just letters, and not an actual word.
-            given_letters_set.chars().sorted().collect_vec()
+        // If the letters are in the form of a known word, that has been
+        // presorted. You know it can be looked up if you originally got
+        // the word by calling self.get_random_word_by_length()
+        let letters_to_find: Vec<char> = match can_be_looked_up {
+            true => self.to_sorted[given_letters_set].clone(),
+            false => given_letters_set.chars().sorted().collect_vec(),
         };
-        // let letters_to_find = &self.to_sorted[given_letters_set];
-        let number_of_letters_to_find = letters_to_find.len();
-
-        // This will hold all of the words that can be build from
-        // the given letters.
-        let mut matching_words: Vec<String> = Vec::new();
-
-        // Flag sets whether or not each dictionary word can be built with the given letters
-        // Starts off true, and the algorithm (loop) can set it to false.
-        let mut this_word_matches;
-        // Used to index into each letter of the dictionary word
-        let mut dictionary_word_pointer;
-        // Used to index into each letter of the letters-set
-        let mut find_word_pointer;
-        // Check each word in the dictionary and compare it with the letters
-        // to see if the dictionary word can be build with a portion of,
-        // or all of, the provided letters.
-        for word in self.all_words.iter() {
-            // Re-Initialize the pointers and flags for each word processed
-            this_word_matches = true;
-            dictionary_word_pointer = 0;
-            find_word_pointer = 0;
-            // Each dictionary word has already been pre-sorted. Just need to access it.
-            let sorted_dictionary_word = &self.to_sorted[word];
-
-            // Compare each letter of both words
-            while dictionary_word_pointer < sorted_dictionary_word.len() {
-                // Letter of the dictionary word
-                let dictionary_letter =
-                    sorted_dictionary_word[dictionary_word_pointer];
-                // Letter of the "find-word" (of the given letters-set)
-                let partial_letter = letters_to_find[find_word_pointer];
-                // If both letters match, then we're making progress and this
-                // dictionary word is one step closer to being a match
-                if dictionary_letter == partial_letter {
-                    dictionary_word_pointer += 1;
-                }
-                // Regardless whether the two letters matched, the find-word letter
-                // will always advance
-                find_word_pointer += 1;
-
-                // If we run out of letters in the letters-set before getting through
-                // all the letters of the dictionary word, then the word could not be
-                // build with the letters-set. It's not a match.
-                if find_word_pointer >= number_of_letters_to_find
-                    && dictionary_word_pointer < sorted_dictionary_word.len()
-                {
-                    // This word cannot be built from the given letters
-                    this_word_matches = false;
-                    break;
-                }
-            }
+        let unique_letters: Vec<char> =
+            letters_to_find.iter().copied().dedup().collect();
+
+        // With lots of letters, there are more combinations than keys
+        let keys_to_check: Vec<&String> =
+            if unique_letters.len() > MAX_LETTERS_FOR_COMBINATIONS {
+                self.sorted_uniques
+                    .keys()
+                    .filter(|key| {
+                        let key_letters: Vec<char> = key.chars().collect();
+                        sorted_letters_fit_within(&key_letters, &unique_letters)
+                    })
+                    .collect()
+            } else {
+                get_all_letter_combinations(&unique_letters)
+                    .iter()
+                    .filter_map(|combination| {
+                        self.sorted_uniques
+                            .get_key_value(combination)
+                            .map(|(key, _)| key)
+                    })
+                    .collect()
+            };
+
+        let mut matching_words: Vec<String> = keys_to_check
+            .into_iter()
+            .flat_map(|key| self.sorted_uniques[key].iter())
+            .filter(|(sorted_word, _)| {
+                let sorted_word: Vec<char> = sorted_word.chars().collect();
+                sorted_letters_fit_within(&sorted_word, &letters_to_find)
+            })
+            .map(|(_, word)| word.to_owned())
+            .collect();
+        matching_words.sort();
+        matching_words.dedup();
+
+        matching_words
+    }
+
+    ///
+    /// Same as `find_all_words_for_letters()`, but any `?` in the letters
+    /// is a wildcard which can be used as any letter. Each match also
+    /// lists which letters the wildcards became.
+    ///
+    /// Example:
+    /// ```
+    /// // [{ word: "cab", wildcard_letters: ['c'] },
+    /// //  { word: "tab", wildcard_letters: [] }, ...]
+    /// dictionary.find_all_words_for_letters_with_wildcards("tab?");
+    /// ```
+    ///
+    pub fn find_all_words_for_letters_with_wildcards(
+        &self,
+        given_letters_set: &str,
+    ) -> Vec<WildcardMatch> {
+        let (number_of_wildcards, letters_to_find) =
+            split_wildcards(given_letters_set);
+
+        // The index can't be used for wildcards, since a wildcard could
+        // be any letter. So every word in the dictionary is checked.
+        self.all_words
+            .iter()
+            .filter_map(|word| {
+                let sorted_dictionary_word = &self.to_sorted[word];
+                let wildcard_letters = sorted_letters_missing_from(
+                    sorted_dictionary_word,
+                    &letters_to_find,
+                );
+                (wildcard_letters.len() <= number_of_wildcards).then(|| {
+                    WildcardMatch {
+                        word: word.to_owned(),
+                        wildcard_letters,
+                    }
+                })
+            })
+            .sorted_by(|a, b| a.word.cmp(&b.word))
+            .collect()
+    }
 
-            if this_word_matches {
-                matching_words.push(word.to_owned());
+    ///
+    /// Finds every word which matches a crossword style pattern, where
+    /// `?` can be any letter. Optionally, the words must also be
+    /// buildable from the given letters (which can include wildcards).
+    ///
+    /// Example:
+    /// ```
+    /// // ["mases", "meses", "mises", "moses", "muses"]
+    /// dictionary.find_words_matching_pattern("m?s?s", None);
+    /// // ["muses"]
+    /// dictionary.find_words_matching_pattern("m?s?s", Some("sumeso"));
+    /// ```
+    ///
+    pub fn find_words_matching_pattern(
+        &self,
+        pattern: &str,
+        letters: Option<&str>,
+    ) -> Vec<String> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let number_of_letters = pattern.len();
+
+        // The words which have each of the known letters in place
+        let mut known_letters: Vec<&Vec<usize>> = Vec::new();
+        for (position, letter) in pattern.iter().enumerate() {
+            if *letter == WILDCARD_LETTER {
+                continue;
+            }
+            match self
+                .by_position
+                .get(&(number_of_letters, position, *letter))
+            {
+                Some(word_indexes) => known_letters.push(word_indexes),
+                None => return Vec::new(),
             }
         }
 
-        matching_words
+        // Start from the rarest known letter, and keep only the
+        // words which have every other known letter as well.
+        known_letters.sort_by_key(|word_indexes| word_indexes.len());
+        let candidates: Vec<&String> = match known_letters.split_first() {
+            Some((rarest, others)) => rarest
+                .iter()
+                .filter(|word_index| {
+                    others
+                        .iter()
+                        .all(|other| other.binary_search(word_index).is_ok())
+                })
+                .map(|word_index| &self.all_words[*word_index])
+                .collect(),
+            None => match self.by_length.get(&number_of_letters) {
+                Some(words) => words.iter().collect(),
+                None => Vec::new(),
+            },
+        };
+
+        let letters = letters.map(split_wildcards);
+        candidates
+            .into_iter()
+            .filter(|word| match &letters {
+                Some((number_of_wildcards, letters_to_find)) => {
+                    sorted_letters_missing_from(
+                        &self.to_sorted[*word],
+                        letters_to_find,
+                    )
+                    .len()
+                        <= *number_of_wildcards
+                }
+                None => true,
+            })
+            .sorted()
+            .dedup()
+            .cloned()
+            .collect()
     }
 
     ///
@@ -234,3 +603,217 @@ impl DictionaryManager {
         self.by_length.get(&number_of_characters)
     }
 }
+
+///
+/// Separates the wildcards from the letters. Returns the number of
+/// wildcards, and the rest of the letters (sorted).
+///
+fn split_wildcards(letters: &str) -> (usize, Vec<char>) {
+    let (wildcards, letters): (Vec<char>, Vec<char>) = letters
+        .chars()
+        .partition(|letter| *letter == WILDCARD_LETTER);
+    (wildcards.len(), letters.into_iter().sorted().collect())
+}
+
+///
+/// Every combination (of any length) of the given letters, keeping
+/// the letters in the same order.
+///
+/// Example:
+/// ```
+/// // ["a", "b", "ab", "c", "ac", "bc", "abc"]
+/// get_all_letter_combinations(&['a', 'b', 'c']);
+/// ```
+///
+fn get_all_letter_combinations(letters: &[char]) -> Vec<String> {
+    (1..1_usize << letters.len())
+        .map(|mask| {
+            letters
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| mask & (1 << index) != 0)
+                .map(|(_, letter)| letter)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_dictionary;
+    use crate::utils::set_random_seed;
+
+    #[test]
+    fn test_find_all_words_for_letters() {
+        let dictionary = make_dictionary("anna ant hant nathan taunt than");
+
+        assert_eq!(
+            dictionary.find_all_words_for_letters("nathan", true),
+            vec!["anna", "ant", "hant", "nathan", "than"]
+        );
+        assert_eq!(
+            dictionary.find_all_words_for_letters("tuant", false),
+            vec!["ant", "taunt"]
+        );
+        assert!(dictionary
+            .find_all_words_for_letters("xyz", false)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_find_all_words_for_letters_with_wildcards() {
+        let dictionary = make_dictionary("ant cab nathan tab taunt than");
+
+        let matches =
+            dictionary.find_all_words_for_letters_with_wildcards("tab?");
+        let words: Vec<(&str, &[char])> = matches
+            .iter()
+            .map(|m| (m.word.as_str(), m.wildcard_letters.as_slice()))
+            .collect();
+        assert_eq!(
+            words,
+            vec![("ant", &['n'][..]), ("cab", &['c']), ("tab", &[])]
+        );
+
+        let two_wildcards =
+            dictionary.find_all_words_for_letters_with_wildcards("nt??");
+        assert_eq!(two_wildcards.len(), 3);
+        assert_eq!(two_wildcards[2].word, "than");
+        assert_eq!(two_wildcards[2].wildcard_letters, vec!['a', 'h']);
+    }
+
+    #[test]
+    fn test_find_words_matching_pattern() {
+        let dictionary = make_dictionary("mass mess miss moss muses mosses");
+
+        assert_eq!(
+            dictionary.find_words_matching_pattern("m?ss", None),
+            vec!["mass", "mess", "miss", "moss"]
+        );
+        assert_eq!(
+            dictionary.find_words_matching_pattern("m?ss", Some("samsie")),
+            vec!["mass", "mess", "miss"]
+        );
+        assert_eq!(
+            dictionary.find_words_matching_pattern("m?ss", Some("smo?s")),
+            vec!["mass", "mess", "miss", "moss"]
+        );
+        assert_eq!(
+            dictionary.find_words_matching_pattern("?????", None),
+            vec!["muses"]
+        );
+        assert!(dictionary
+            .find_words_matching_pattern("x?ss", None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_anchor_partners() {
+        let mut dictionary = DictionaryManager::new();
+        dictionary.parse_precomputed_dictionary(
+            r#"{"denmark":[],"admired":[],"abdomen":[],"mailbox":[]}"#,
+        );
+
+        // "admired" adds 'i' and 'd', "abdomen" adds 'b' and 'o'
+        assert_eq!(
+            dictionary.get_anchor_partners("denmark", 9),
+            vec!["abdomen", "admired"]
+        );
+        assert!(dictionary.get_anchor_partners("denmark", 8).is_empty());
+        assert!(dictionary.get_anchor_partners("mailbox", 9).is_empty());
+    }
+
+    #[test]
+    fn test_themed_starting_words() {
+        let mut dictionary = DictionaryManager::new();
+        dictionary.parse_precomputed_dictionary(
+            r#"{
+                "bearded": ["bead", "bear", "dare", "deer", "read"],
+                "mailbox": ["bail", "boil", "limbo"],
+                "misuses": ["emus", "mess", "muse", "sums"]
+            }"#,
+        );
+        let themes_directory =
+            std::env::temp_dir().join(format!("themes_{}", std::process::id()));
+        fs::create_dir_all(&themes_directory).unwrap();
+        fs::write(themes_directory.join("animals.txt"), "bear\ndeer\nemus\n")
+            .unwrap();
+        dictionary.load_theme_files(themes_directory.to_str().unwrap());
+        fs::remove_dir_all(&themes_directory).unwrap();
+
+        let words = ["bear", "mess", "deer", "limbo"].map(String::from);
+        assert_eq!(
+            dictionary.get_themed_words("animals", &words),
+            vec!["bear", "deer"]
+        );
+        assert!(dictionary.get_themed_words("food", &words).is_empty());
+
+        // "bearded" builds 2 of the animals, and "misuses" only 1
+        set_random_seed(Some(3));
+        let chosen_words: Vec<String> = (0..300)
+            .map(|_| {
+                let (word, _) = dictionary
+                    .get_random_starting_word_for_theme("animals", 1)
+                    .unwrap();
+                word.to_owned()
+            })
+            .collect();
+        let count = |word| chosen_words.iter().filter(|w| *w == word).count();
+        assert_eq!(count("bearded") + count("misuses"), 300);
+        assert!(count("misuses") > 50 && count("bearded") > 150);
+
+        let (word, _) = dictionary
+            .get_random_starting_word_for_theme("animals", 2)
+            .unwrap();
+        assert_eq!(word, "bearded");
+        assert!(dictionary
+            .get_random_starting_word_for_theme("animals", 3)
+            .is_none());
+        assert!(dictionary
+            .get_random_starting_word_for_theme("food", 1)
+            .is_none());
+        set_random_seed(None);
+    }
+
+    #[test]
+    fn test_sorted_uniques_file_round_trip() {
+        let words = "anna hant nathan than taunt mailbox limbo bail boil";
+        let dictionary = make_dictionary(words);
+        let filepath = std::env::temp_dir()
+            .join(format!("sorted_uniques_{}.json", std::process::id()));
+        let filepath = filepath.to_str().unwrap();
+
+        dictionary.write_sorted_uniques_file(filepath);
+        let mut reloaded = DictionaryManager::new();
+        reloaded.load_sorted_uniques_file(filepath);
+        fs::remove_file(filepath).unwrap();
+
+        for letters in ["nathan", "mailbox", "tuanth", "oblim"] {
+            assert_eq!(
+                reloaded.find_all_words_for_letters(letters, false),
+                dictionary.find_all_words_for_letters(letters, false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_go_sorted_uniques_file() {
+        // Written by the Go generator, which also keeps 3 letter words
+        let go_json =
+            r#"{"ab":[["aab","aba"],["aabb","baba"]],"abc":[["abc","cab"]]}"#;
+        let filepath = std::env::temp_dir()
+            .join(format!("go_sorted_uniques_{}.json", std::process::id()));
+        let filepath = filepath.to_str().unwrap();
+        fs::write(filepath, go_json).unwrap();
+
+        let mut dictionary = DictionaryManager::new();
+        dictionary.load_sorted_uniques_file(filepath);
+        fs::remove_file(filepath).unwrap();
+
+        assert_eq!(
+            dictionary.find_all_words_for_letters("abbac", false),
+            vec!["baba"]
+        );
+    }
+}
@@ -1,13 +1,47 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs::{self, File},
     io::{BufRead, BufReader},
     path::Path,
 };
 
 use crate::config::Config;
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use super::DictionaryManager;
+use super::{
+    is_valid_word, normalize_word, DictionaryManager, Language,
+    NormalizationPolicy, ProfanityFilter,
+};
+use crate::utils::word_length;
+
+/// Words shorter or longer than this never make it into
+/// the clean dictionary.
+pub const MIN_WORD_LENGTH: usize = 4;
+pub const MAX_WORD_LENGTH: usize = 7;
+
+///
+/// The "sorted unique letters" index, which is the format of the
+/// `sorted_uniques.json` file created by the Go dictionary generator
+/// (and used by the word solver app).
+///
+/// Each key is the unique letters of a word, sorted. Each value
+/// lists every word with exactly those unique letters, as pairs
+/// of `[sorted letters, word]`.
+///
+/// Example: `{ "ab": [["aab", "aba"], ["aabb", "baba"]] }`
+///
+pub type SortedUniquesMap = HashMap<String, Vec<(String, String)>>;
+
+///
+/// The settings a "Pre-Computed SubWords file" was built with. The file
+/// can only be updated incrementally if they haven't changed since.
+///
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PrecomputedSettings {
+    min_words_necessary_to_save: usize,
+}
 
 // ==============Dictionary File Utilties==============
 
@@ -30,6 +64,47 @@ pub fn read_clean_dictionary(dictionary_filepath: &str) -> Vec<String> {
     clean_words
 }
 
+///
+/// Reads every theme file within a directory. Each theme file
+/// is a list of words (one per line) that belong to the theme.
+/// The name of the file is the name of the theme.
+///
+/// Example: `./themes/animals.txt` -> `{ "animals": {"bear", "lion"} }`
+///
+/// The themes are optional, so if the directory doesn't exist then
+/// there simply are no themes.
+///
+pub fn read_theme_files(
+    themes_directory: &str,
+) -> HashMap<String, HashSet<String>> {
+    let mut themes = HashMap::new();
+
+    let Ok(theme_files) = fs::read_dir(themes_directory) else {
+        return themes;
+    };
+
+    for theme_file in theme_files.map_while(Result::ok) {
+        let theme_path = theme_file.path();
+        if theme_path.extension().and_then(|ext| ext.to_str()) != Some("txt") {
+            continue;
+        }
+        if let Some(theme_name) =
+            theme_path.file_stem().and_then(|name| name.to_str())
+        {
+            let theme_words = read_clean_dictionary(
+                theme_path.to_str().expect("Invalid theme file path"),
+            )
+            .into_iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+            themes.insert(theme_name.to_lowercase(), theme_words);
+        }
+    }
+
+    themes
+}
+
 ///
 /// Creates a new "clean" dictionary file on disk.
 ///
@@ -39,25 +114,20 @@ pub fn read_clean_dictionary(dictionary_filepath: &str) -> Vec<String> {
 /// __The process looks like:__
 ///     1) Read in the base dictionary file with unfiltered words
 ///     2) Remove any words that are too short or too long
-///     3) Remove any cuss words that are found within the "bad words" file
-///     4) Remove any weird symbols or spaces from words
-///     5) Write the "clean" dictionary file back to disk.
+///     3) Remove any cuss words, or words built from cuss words,
+///        according to the profanity filter
+///     4) Remove any weird symbols or spaces from words, and normalize
+///        the letters (Unicode NFC, maybe strip accents)
+///     5) Remove words with letters outside of the language's alphabet
+///     6) Write the "clean" dictionary file back to disk.
 ///
 fn process_new_clean_dictionary(
     base_dictionary_file_path: &str,
-    bad_words_file_path: &str,
+    profanity_filter: &ProfanityFilter,
+    language: Language,
+    normalization: NormalizationPolicy,
     output_dictionary_file_path: &str,
 ) {
-    // Blacklist File
-    // Contains the "bad words" that should be excluded
-    let blacklist_file = File::open(bad_words_file_path)
-        .expect("Could not locate the bad words file!");
-    let blacklist_words_set: HashSet<String> = BufReader::new(blacklist_file)
-        .lines()
-        .map(|l| l.expect("Could not parse line of bad words"))
-        .map(|word| word.trim().to_lowercase())
-        .collect();
-
     // Base Dictionary File
     // Contains the unfiltered words that need to be filtered
     let dictionary_file = File::open(base_dictionary_file_path)
@@ -65,22 +135,25 @@ fn process_new_clean_dictionary(
     let dictionary_reader = BufReader::new(dictionary_file);
 
     // Process all of the words to create the "Clean Dictionary Words"
+    // A handful of lines in the base dictionary aren't valid UTF-8. Those
+    // lines are skipped, rather than ending the read early.
     let mut clean_words = Vec::new();
-    for word in dictionary_reader.lines().flatten() {
-        // Clean up the string, remove spaces, remove non a-z characters
-        let trimmed_word: String = word
-            .trim()
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .collect();
+    let base_words = dictionary_reader
+        .split(b'\n')
+        .map(|line| line.expect("Could not read the base dictionary file."))
+        .filter_map(|line| String::from_utf8(line).ok());
+    for word in base_words {
+        // Clean up the string, remove spaces, remove non-letter characters
+        let trimmed_word = normalize_word(&word, language, normalization);
+        let number_of_letters = word_length(&trimmed_word);
 
         // Only keep the word if it is an appropriate length and
-        // exclude any "bad words" found within the bad_words dictionary.
-        if trimmed_word.len() > 3
-            && trimmed_word.len() < 8
-            && !blacklist_words_set.contains(&trimmed_word)
+        // exclude any "bad words" caught by the profanity filter.
+        if (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&number_of_letters)
+            && is_valid_word(&trimmed_word, language)
+            && !profanity_filter.is_profane(&trimmed_word)
         {
-            clean_words.push(trimmed_word.to_lowercase());
+            clean_words.push(trimmed_word);
         }
     }
 
@@ -95,46 +168,331 @@ fn process_new_clean_dictionary(
 /// each word to a list of all of the words that can be constructed
 /// using all or some of the letters of the original word.
 ///
+/// The file is written atomically. The JSON is written to a temporary
+/// file first, which then replaces the old file. So if anything goes
+/// wrong part way through, the old file is still intact.
+///
 fn write_precomputed_file(
     filepath: &str,
     precomputed_words: &HashMap<String, Vec<String>>,
 ) {
     if let Ok(json_to_write) = serde_json::to_string(precomputed_words) {
-        fs::write(filepath, json_to_write)
+        let temporary_filepath = format!("{filepath}.tmp");
+        fs::write(&temporary_filepath, json_to_write)
             .expect("Could not write the precomputed word-map file.");
+        fs::rename(&temporary_filepath, filepath)
+            .expect("Could not replace the precomputed word-map file.");
     }
 }
 
 ///
+/// Reads an existing "Precomputed Words File", if there is one.
+///
+pub fn read_precomputed_file(
+    filepath: &str,
+) -> Option<HashMap<String, Vec<String>>> {
+    let precomputed_json = fs::read_to_string(filepath).ok()?;
+    serde_json::from_str(&precomputed_json).ok()
+}
+
+///
+/// Writes the settings that the "Pre-Computed SubWords file" was built with.
+///
+fn write_precomputed_settings_file(
+    filepath: &str,
+    precomputed_settings: &PrecomputedSettings,
+) {
+    if let Ok(json_to_write) = serde_json::to_string(precomputed_settings) {
+        fs::write(filepath, json_to_write)
+            .expect("Could not write the precomputed settings file.");
+    }
+}
+
+///
+/// Reads the settings of the existing "Pre-Computed SubWords file",
+/// if there are any.
+///
+fn read_precomputed_settings_file(
+    filepath: &str,
+) -> Option<PrecomputedSettings> {
+    let settings_json = fs::read_to_string(filepath).ok()?;
+    serde_json::from_str(&settings_json).ok()
+}
+
+///
+/// Sorts the letters of a word, and also returns only the unique
+/// letters of the sorted word.
+///
+/// Example:
+/// ```
+/// get_sorted_uniques("banana"); // ("abn", "aaabnn")
+/// ```
+///
+pub fn get_sorted_uniques(word: &str) -> (String, String) {
+    let sorted: String = word.chars().sorted().collect();
+    let uniques: String = sorted.chars().dedup().collect();
+    (uniques, sorted)
+}
+
+///
+/// Builds the "sorted unique letters" index from a list of words.
+/// See `SortedUniquesMap`.
+///
+pub fn create_sorted_uniques_map(words: &[String]) -> SortedUniquesMap {
+    let mut sorted_uniques_map = SortedUniquesMap::new();
+    for word in words.iter() {
+        let (uniques, sorted) = get_sorted_uniques(word);
+        sorted_uniques_map
+            .entry(uniques)
+            .or_default()
+            .push((sorted, word.to_owned()));
+    }
+    sorted_uniques_map
+}
+
+///
+/// Writes the "sorted unique letters" index to a JSON file. The keys
+/// are written in sorted order, the same as the Go generator does, so
+/// that both produce the exact same file.
+///
+pub fn write_sorted_uniques_file(
+    filepath: &str,
+    sorted_uniques_map: &SortedUniquesMap,
+) {
+    let sorted_keys: BTreeMap<&String, &Vec<(String, String)>> =
+        sorted_uniques_map.iter().collect();
+    if let Ok(json_to_write) = serde_json::to_string(&sorted_keys) {
+        let temporary_filepath = format!("{filepath}.tmp");
+        fs::write(&temporary_filepath, json_to_write)
+            .expect("Could not write the sorted uniques file.");
+        fs::rename(&temporary_filepath, filepath)
+            .expect("Could not replace the sorted uniques file.");
+    }
+}
+
+///
+/// Reads a "sorted unique letters" index file, such as the one
+/// created by the Go dictionary generator.
+///
+pub fn read_sorted_uniques_file(filepath: &str) -> Option<SortedUniquesMap> {
+    let sorted_uniques_json = fs::read_to_string(filepath).ok()?;
+    serde_json::from_str(&sorted_uniques_json).ok()
+}
+
+///
+/// Finds all of the subwords of a single seven-letter word. Returns
+/// `None` if it can't build at least `min_words_necessary_to_save` words.
+///
+fn find_subwords_for_seven_letter_word(
+    one_seven_letter_word: &str,
+    min_words_necessary_to_save: usize,
+    dictionary: &DictionaryManager,
+) -> Option<Vec<String>> {
+    //
+    let mut all_words_for_letters =
+        dictionary.find_all_words_for_letters(one_seven_letter_word, true);
+
+    //
+    all_words_for_letters.retain(|word| word != one_seven_letter_word);
+
+    //
+    (all_words_for_letters.len() >= min_words_necessary_to_save)
+        .then_some(all_words_for_letters)
+}
+
+///
+/// Maps every seven-letter word to all of the words that can be
+/// constructed from its letters. Seven-letter words that can't build
+/// at least `min_words_necessary_to_save` words are left out.
+///
+/// Each seven-letter word is independent of the others, so they
+/// are processed in parallel.
 ///
 fn find_all_subwords_for_all_seven_letter_words(
     min_words_necessary_to_save: usize,
     dictionary: &DictionaryManager,
 ) -> HashMap<String, Vec<String>> {
     // Build a new precomputed words map
-    let mut precomputed_words_map: HashMap<String, Vec<String>> =
-        HashMap::new();
-    if let Some(all_seven_letter_words) = dictionary.get_all_words_of_length(7)
-    {
-        for one_seven_letter_word in all_seven_letter_words.iter() {
-            //
-            let mut all_words_for_letters = dictionary
-                .find_all_words_for_letters(one_seven_letter_word, true);
-
-            //
-            all_words_for_letters.retain(|word| word != one_seven_letter_word);
-
-            //
-            if all_words_for_letters.len() >= min_words_necessary_to_save {
-                precomputed_words_map.insert(
-                    one_seven_letter_word.to_string(),
-                    all_words_for_letters,
-                );
+    match dictionary.get_all_words_of_length(7) {
+        Some(all_seven_letter_words) => all_seven_letter_words
+            .par_iter()
+            .filter_map(|one_seven_letter_word| {
+                let subwords = find_subwords_for_seven_letter_word(
+                    one_seven_letter_word,
+                    min_words_necessary_to_save,
+                    dictionary,
+                )?;
+                Some((one_seven_letter_word.to_string(), subwords))
+            })
+            .collect(),
+        None => HashMap::new(),
+    }
+}
+
+///
+/// Updates an existing precomputed words map after the clean dictionary
+/// has changed, rather than rebuilding the whole thing.
+///
+/// Only the seven-letter words whose letters could build one of the added
+/// or removed words need to be recomputed. Every other seven-letter word
+/// would end up with the exact same list of subwords.
+///
+/// If the previous map was built with a different (or an unknown)
+/// `min_words_necessary_to_save`, then the seven-letter words which
+/// didn't change could still be wrong. So the map is rebuilt instead.
+///
+fn update_subwords_for_changed_words(
+    min_words_necessary_to_save: usize,
+    dictionary: &DictionaryManager,
+    previous_words: &[String],
+    current_words: &[String],
+    mut previous_map: HashMap<String, Vec<String>>,
+    previous_settings: Option<PrecomputedSettings>,
+) -> HashMap<String, Vec<String>> {
+    let settings = PrecomputedSettings {
+        min_words_necessary_to_save,
+    };
+    if previous_settings.as_ref() != Some(&settings) {
+        println!("The precomputed settings have changed, rebuilding.");
+        return find_all_subwords_for_all_seven_letter_words(
+            min_words_necessary_to_save,
+            dictionary,
+        );
+    }
+
+    let previous_set: HashSet<&String> = previous_words.iter().collect();
+    let current_set: HashSet<&String> = current_words.iter().collect();
+
+    // Words that were either added to, or removed from, the dictionary
+    let changed_words: Vec<Vec<char>> = previous_set
+        .symmetric_difference(&current_set)
+        .map(|word| word.chars().sorted().collect())
+        .collect();
+
+    // Removed seven-letter words can't be starting words anymore
+    previous_map.retain(|starting_word, _| current_set.contains(starting_word));
+
+    let all_seven_letter_words = match dictionary.get_all_words_of_length(7) {
+        Some(words) => words,
+        None => return HashMap::new(),
+    };
+
+    // Recompute each seven-letter word that could build a changed word.
+    // This also covers newly added seven-letter words, since every word
+    // can build itself.
+    let recomputed: Vec<(String, Option<Vec<String>>)> = all_seven_letter_words
+        .par_iter()
+        .filter(|one_seven_letter_word| {
+            let sorted_letters: Vec<char> =
+                one_seven_letter_word.chars().sorted().collect();
+            changed_words.iter().any(|changed_word| {
+                sorted_letters_fit_within(changed_word, &sorted_letters)
+            })
+        })
+        .map(|one_seven_letter_word| {
+            let subwords = find_subwords_for_seven_letter_word(
+                one_seven_letter_word,
+                min_words_necessary_to_save,
+                dictionary,
+            );
+            (one_seven_letter_word.to_string(), subwords)
+        })
+        .collect();
+
+    println!(
+        "Recomputing {} of {} seven-letter words ({} changed words).",
+        recomputed.len(),
+        all_seven_letter_words.len(),
+        changed_words.len()
+    );
+
+    for (one_seven_letter_word, subwords) in recomputed {
+        match subwords {
+            Some(subwords) => {
+                previous_map.insert(one_seven_letter_word, subwords);
             }
+            None => {
+                previous_map.remove(&one_seven_letter_word);
+            }
+        }
+    }
+
+    previous_map
+}
+
+///
+/// Checks if every letter of `sorted_subset` can be found within
+/// `sorted_superset`, at least as many times. Both must already
+/// be sorted.
+///
+/// Example:
+/// ```
+/// sorted_letters_fit_within(&['a', 'n'], &['a', 'a', 'h', 'n', 'n', 't']); // true
+/// sorted_letters_fit_within(&['n', 'n', 'n'], &['a', 'a', 'h', 'n', 'n', 't']); // false
+/// ```
+///
+pub fn sorted_letters_fit_within(
+    sorted_subset: &[char],
+    sorted_superset: &[char],
+) -> bool {
+    let mut superset_letters = sorted_superset.iter();
+    sorted_subset.iter().all(|subset_letter| {
+        superset_letters.any(|superset_letter| superset_letter == subset_letter)
+    })
+}
+
+///
+/// Finds the letters of `sorted_subset` which are missing from
+/// `sorted_superset` (counting repeated letters). Both must already
+/// be sorted.
+///
+/// Example:
+/// ```
+/// // ['a', 'b']
+/// sorted_letters_missing_from(&['a', 'a', 'b', 'n'], &['a', 'n', 't']);
+/// ```
+///
+pub fn sorted_letters_missing_from(
+    sorted_subset: &[char],
+    sorted_superset: &[char],
+) -> Vec<char> {
+    let mut missing_letters = Vec::new();
+    let mut superset_letters = sorted_superset.iter().peekable();
+    for subset_letter in sorted_subset {
+        // Skip past the superset letters which come before this one
+        while superset_letters
+            .next_if(|letter| *letter < subset_letter)
+            .is_some()
+        {}
+        if superset_letters.next_if_eq(&subset_letter).is_none() {
+            missing_letters.push(*subset_letter);
         }
     }
+    missing_letters
+}
 
-    precomputed_words_map
+///
+/// Combines two sets of letters into the fewest letters which can
+/// build both of them. Each letter is kept as many times as the set
+/// which uses it the most. Both must already be sorted.
+///
+/// Example:
+/// ```
+/// // ['a', 'a', 'h', 'n', 'n', 't', 'u']
+/// combine_sorted_letters(&['a', 'a', 'h', 'n', 'n', 't'], &['a', 'n', 't', 'u']);
+/// ```
+///
+pub fn combine_sorted_letters(
+    sorted_letters: &[char],
+    other_sorted_letters: &[char],
+) -> Vec<char> {
+    let mut combined_letters = sorted_letters.to_vec();
+    combined_letters.extend(sorted_letters_missing_from(
+        other_sorted_letters,
+        sorted_letters,
+    ));
+    combined_letters.sort();
+    combined_letters
 }
 
 ///
@@ -147,38 +505,210 @@ fn find_all_subwords_for_all_seven_letter_words(
 ///   by content, meaning, or character length. Just a raw .txt dictionary file.
 /// - There is a `"Bad Words File"`. The words in this are primarily
 ///   inappropriate cuss words, but could also just be any word that we don't
-///   want to appear in the game. The words from this file will never appear
-///   in the finalize "clean dictionary file". This is a basic .txt. file.
+///   want to appear in the game. The words from this file (and words built
+///   from them) will never appear in the finalize "clean dictionary file".
+///   This is a basic .txt. file.
+/// - There is a `"Profanity Allowlist File"`. Words which the profanity
+///   filter mistakes for bad words, such as "glasses" (contains "asses").
+/// - There is a `"Sorted Uniques File"`. This is a .json file that maps the
+///   sorted unique letters of words to the words themselves. It is the same
+///   format that the Go dictionary generator creates.
+///   `Example: { ab: [["aab", "aba"], ["aabb", "baba"]] }`
 /// - There is a `"Pre-Computed SubWords file"`. This is a .json file that maps
 ///   every possible "starting word", to a list of all the words that can be
 ///   constructed by using some or all of the letters of the starting word.
 ///   `Example: { are: ["era", "ear"] }`
 ///
 pub fn create_all_dictionary_files(all_config: &Config) {
+    // 0) Hold on to the previous clean dictionary (if there is one), so
+    //    we can tell which words are being added or removed.
+    let previous_words = Path::new(&all_config.app.clean_dictionary_filepath)
+        .exists()
+        .then(|| {
+            read_clean_dictionary(&all_config.app.clean_dictionary_filepath)
+        });
+
     // 1) Process the base dictionary files, create a
     //    new "clean" dictionary file, and write to disk
+    let profanity_filter = ProfanityFilter::from_files(
+        &all_config.app.bad_words_filepath,
+        &all_config.app.profanity_allowlist_filepath,
+        all_config.app.min_profanity_substring_length,
+    );
     process_new_clean_dictionary(
         &all_config.app.base_dictionary_filepath,
-        &all_config.app.bad_words_filepath,
+        &profanity_filter,
+        all_config.app.language,
+        all_config.app.normalization,
         &all_config.app.clean_dictionary_filepath,
     );
 
     // 2) Instantiate a new DictionaryManager and tell it to read in
     //    the brand new "Clean Dictionary" file
+    let current_words =
+        read_clean_dictionary(&all_config.app.clean_dictionary_filepath);
     let mut dictionary = DictionaryManager::default();
-    dictionary.load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+    dictionary.set_language(all_config.app.language);
+    dictionary.load_words(current_words.clone());
 
-    // 3) Calculate every word that can be constructed from every seven-letter word.
+    // 3) Write the "sorted unique letters" index, so that the same
+    //    dictionary can be used by the word solver app
+    dictionary
+        .write_sorted_uniques_file(&all_config.app.sorted_uniques_filepath);
+
+    // 4) Calculate every word that can be constructed from every seven-letter word.
     //    Store this in a HashMap (which will be written to a JSON file in a moment)
-    let all_subwords_map = find_all_subwords_for_all_seven_letter_words(
-        all_config.generator.min_words_to_consider_letters,
-        &dictionary,
-    );
+    //    If the previous files are around, only the words affected by the
+    //    changes to the clean dictionary need to be recomputed.
+    let min_words_necessary_to_save =
+        all_config.generator.min_words_to_consider_letters;
+    let settings_filepath = &all_config.app.precomputed_settings_filepath;
+    let previous_map =
+        read_precomputed_file(&all_config.app.precomputed_words_filepath);
+    let all_subwords_map = match (previous_words, previous_map) {
+        (Some(previous_words), Some(previous_map)) => {
+            update_subwords_for_changed_words(
+                min_words_necessary_to_save,
+                &dictionary,
+                &previous_words,
+                &current_words,
+                previous_map,
+                read_precomputed_settings_file(settings_filepath),
+            )
+        }
+        _ => find_all_subwords_for_all_seven_letter_words(
+            min_words_necessary_to_save,
+            &dictionary,
+        ),
+    };
 
-    // 4) JSON Serialize the pre-computed map of every subword that can be built
+    // 5) JSON Serialize the pre-computed map of every subword that can be built
     //    from the letters of every seven-letter word. Then write it to a JSON file.
+    //    The old settings are removed first, so they can't be mistaken for
+    //    the settings of the new file if writing it fails.
+    if Path::new(settings_filepath).exists() {
+        fs::remove_file(settings_filepath)
+            .expect("Could not remove the precomputed settings file.");
+    }
     write_precomputed_file(
         &all_config.app.precomputed_words_filepath,
         &all_subwords_map,
-    )
+    );
+    write_precomputed_settings_file(
+        settings_filepath,
+        &PrecomputedSettings {
+            min_words_necessary_to_save,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_dictionary;
+
+    fn to_words(words: &str) -> Vec<String> {
+        words.split(' ').map(|word| word.to_owned()).collect()
+    }
+
+    #[test]
+    fn test_sorted_letters_fit_within() {
+        let superset = ['a', 'a', 'h', 'n', 'n', 't'];
+        assert!(sorted_letters_fit_within(&['a', 'n'], &superset));
+        assert!(sorted_letters_fit_within(&['a', 'n', 'n'], &superset));
+        assert!(!sorted_letters_fit_within(&['n', 'n', 'n'], &superset));
+        assert!(!sorted_letters_fit_within(&['b'], &superset));
+    }
+
+    #[test]
+    fn test_sorted_letters_missing_from() {
+        let superset = ['a', 'h', 'n', 'n', 't'];
+        assert!(
+            sorted_letters_missing_from(&['a', 'n', 'n'], &superset).is_empty()
+        );
+        assert_eq!(
+            sorted_letters_missing_from(&['a', 'a', 'b', 'n', 'z'], &superset),
+            vec!['a', 'b', 'z']
+        );
+    }
+
+    #[test]
+    fn test_combine_sorted_letters() {
+        let nathan = ['a', 'a', 'h', 'n', 'n', 't'];
+        assert_eq!(
+            combine_sorted_letters(&nathan, &['a', 'n', 't', 'u']),
+            vec!['a', 'a', 'h', 'n', 'n', 't', 'u']
+        );
+        assert_eq!(combine_sorted_letters(&nathan, &['a', 'n']), nathan);
+    }
+
+    #[test]
+    fn test_incremental_update_matches_full_rebuild() {
+        let previous = "emus mess miss misuses muse sums mailbox bail boil";
+        let current = "emus mess misuses muse sums uses mailbox bail limbo";
+        let previous_words = to_words(previous);
+        let current_words = to_words(current);
+
+        let previous_map = find_all_subwords_for_all_seven_letter_words(
+            1,
+            &make_dictionary(previous),
+        );
+        let current_dictionary = make_dictionary(current);
+
+        let full_rebuild = find_all_subwords_for_all_seven_letter_words(
+            1,
+            &current_dictionary,
+        );
+        let incremental = update_subwords_for_changed_words(
+            1,
+            &current_dictionary,
+            &previous_words,
+            &current_words,
+            previous_map,
+            Some(PrecomputedSettings {
+                min_words_necessary_to_save: 1,
+            }),
+        );
+
+        assert_eq!(incremental, full_rebuild);
+        assert_eq!(
+            incremental["misuses"],
+            to_words("emus mess muse sums uses")
+        );
+    }
+
+    #[test]
+    fn test_incremental_update_with_changed_settings() {
+        // "abdomen" (2 subwords) isn't affected by the changed words
+        let previous = "emus mess miss misuses muse sums abdomen bone mode";
+        let current = "emus mess misuses muse sums uses abdomen bone mode";
+        let previous_map = find_all_subwords_for_all_seven_letter_words(
+            1,
+            &make_dictionary(previous),
+        );
+        let current_dictionary = make_dictionary(current);
+        let update = |previous_settings| {
+            update_subwords_for_changed_words(
+                3,
+                &current_dictionary,
+                &to_words(previous),
+                &to_words(current),
+                previous_map.clone(),
+                previous_settings,
+            )
+        };
+
+        let full_rebuild = find_all_subwords_for_all_seven_letter_words(
+            3,
+            &current_dictionary,
+        );
+        assert!(!full_rebuild.contains_key("abdomen"));
+        assert_eq!(
+            update(Some(PrecomputedSettings {
+                min_words_necessary_to_save: 1
+            })),
+            full_rebuild
+        );
+        assert_eq!(update(None), full_rebuild);
+    }
 }
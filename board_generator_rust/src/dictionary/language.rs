@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::utils::word_length;
+
+///
+/// The language of a dictionary. Each language has its own
+/// alphabet, so it determines which letters a word is allowed
+/// to contain, and which letters must survive accent folding.
+///
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+impl Language {
+    ///
+    /// The ISO 639-1 code of the language. This is what gets
+    /// written to the generated boards.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    ///
+    /// Letters (besides a-z) which belong to the alphabet
+    /// of the language.
+    ///
+    pub fn extra_letters(&self) -> &'static [char] {
+        match self {
+            Language::English => &[],
+            Language::Spanish => &['á', 'é', 'í', 'ó', 'ú', 'ü', 'ñ'],
+            Language::German => &['ä', 'ö', 'ü', 'ß'],
+            Language::French => &[
+                'à', 'â', 'æ', 'ç', 'é', 'è', 'ê', 'ë', 'î', 'ï', 'ô', 'œ',
+                'ù', 'û', 'ü', 'ÿ',
+            ],
+        }
+    }
+
+    ///
+    /// Letters which are considered their own letter, rather than
+    /// an accented version of another letter. These are never
+    /// folded. Ex: In Spanish, "año" and "ano" are different words.
+    ///
+    pub fn protected_letters(&self) -> &'static [char] {
+        match self {
+            Language::English | Language::French => &[],
+            Language::Spanish => &['ñ'],
+            Language::German => &['ä', 'ö', 'ü', 'ß'],
+        }
+    }
+
+    ///
+    /// Checks if the letter belongs to the alphabet of the language.
+    ///
+    pub fn is_letter(&self, letter: char) -> bool {
+        letter.is_ascii_lowercase() || self.extra_letters().contains(&letter)
+    }
+}
+
+///
+/// Describes how words should be normalized before they are
+/// stored in (or looked up from) a dictionary.
+///
+/// Words are always lowercased and converted to NFC, so that
+/// an accented letter is always a single `char`, which is
+/// what the board's grid expects.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NormalizationPolicy {
+    /// Strip the accents from letters, unless the letter is
+    /// protected by the language. Ex: "café" -> "cafe"
+    pub fold_accents: bool,
+}
+
+///
+/// Normalizes a word according to the language and the policy.
+/// Any symbols or spaces are removed from the word.
+///
+/// Example:
+/// ```
+/// let policy = NormalizationPolicy { fold_accents: true };
+/// // "nino"
+/// normalize_word("Niño", Language::English, policy);
+/// // "niño"
+/// normalize_word("Niño", Language::Spanish, policy);
+/// ```
+///
+pub fn normalize_word(
+    word: &str,
+    language: Language,
+    policy: NormalizationPolicy,
+) -> String {
+    word.trim()
+        .to_lowercase()
+        .nfc()
+        .filter(|letter| letter.is_alphabetic())
+        .flat_map(|letter| {
+            if policy.fold_accents
+                && !letter.is_ascii()
+                && !language.protected_letters().contains(&letter)
+            {
+                // Decompose the letter ('é' -> 'e' + '´'), and
+                // keep only the base letter
+                letter.nfd().filter(|c| !is_combining_mark(*c)).collect()
+            } else {
+                vec![letter]
+            }
+        })
+        .collect()
+}
+
+///
+/// Checks that every letter of a (normalized) word belongs to
+/// the alphabet of the language, and that each letter on screen
+/// is exactly one `char`.
+///
+pub fn is_valid_word(word: &str, language: Language) -> bool {
+    word.chars().all(|letter| language.is_letter(letter))
+        && word_length(word) == word.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_word() {
+        let keep_accents = NormalizationPolicy {
+            fold_accents: false,
+        };
+        let fold_accents = NormalizationPolicy { fold_accents: true };
+
+        // "e" followed by a combining accent
+        let decomposed_cafe = "cafe\u{301}";
+        assert_eq!(
+            normalize_word(decomposed_cafe, Language::French, keep_accents),
+            "café"
+        );
+        assert_eq!(
+            normalize_word("Café", Language::French, fold_accents),
+            "cafe"
+        );
+        assert_eq!(
+            normalize_word("Niño", Language::Spanish, fold_accents),
+            "niño"
+        );
+        assert_eq!(
+            normalize_word("Straße", Language::German, fold_accents),
+            "straße"
+        );
+        assert_eq!(
+            normalize_word("don't", Language::English, keep_accents),
+            "dont"
+        );
+    }
+
+    #[test]
+    fn test_is_valid_word() {
+        assert!(is_valid_word("niño", Language::Spanish));
+        assert!(!is_valid_word("niño", Language::English));
+        assert!(!is_valid_word("nin\u{303}o", Language::Spanish));
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::board::{Direction, EMPTY_CELL_VALUE};
+use crate::generator::WordLocationsOnBoard;
+use crate::utils::word_length;
+
+/// Stems shorter than this are too ambiguous to compare. For instance
+/// "raped" and "rapped" would both stem down to "rap".
+const MIN_STEM_LENGTH: usize = 4;
+
+/// Suffixes that get stripped from a word to find its stem. The order
+/// matters, the longest suffixes need to be tried first.
+const INFLECTION_SUFFIXES: [&str; 7] =
+    ["ings", "ing", "ies", "ied", "es", "ed", "s"];
+
+///
+/// Decides whether or not a word is offensive. The "bad words" file
+/// only contains root words, so matching on exact membership isn't
+/// enough. The filter will also catch:
+///
+/// - Inflections of a bad word. `"jerk"` -> `"jerks"`, `"jerked"`
+/// - Words whose stem is a bad word. `"jerkings"` -> `"jerk"`
+/// - Words which contain a (long enough) bad word within them.
+///
+/// Matching on substrings and stems creates false positives, such as
+/// "glasses" containing "asses". Any word in the allowlist will
+/// never be considered offensive.
+///
+#[derive(Debug, Default, Clone)]
+pub struct ProfanityFilter {
+    /// Every word from the "bad words" file, exactly as written
+    blacklist: HashSet<String>,
+    /// Every inflection (plurals, past tense...) of the bad words
+    inflections: HashSet<String>,
+    /// Bad words that are long enough to be searched for within
+    /// other words without causing too many false positives.
+    substrings: Vec<String>,
+    /// Words that should never be filtered out
+    allowlist: HashSet<String>,
+}
+
+// ===============Static Methods=================
+
+impl ProfanityFilter {
+    ///
+    /// Builds a new filter from a list of bad words and a list of
+    /// allowed words (false positives).
+    ///
+    /// * `min_substring_length` - Bad words with at least this many
+    ///   letters will also be searched for within other words.
+    ///
+    pub fn new(
+        bad_words: Vec<String>,
+        allowed_words: Vec<String>,
+        min_substring_length: usize,
+    ) -> Self {
+        let mut filter = Self::default();
+
+        for bad_word in bad_words.iter().map(|w| normalize_word(w)) {
+            // Phrases (multiple words) can only ever be matched exactly
+            let is_single_word = bad_word.chars().all(|c| c.is_alphabetic());
+            if is_single_word {
+                filter.inflections.extend(get_inflections(&bad_word));
+
+                if word_length(&bad_word) >= min_substring_length {
+                    filter.substrings.push(bad_word.to_owned());
+                }
+            }
+            filter.blacklist.insert(bad_word);
+        }
+
+        filter.allowlist =
+            allowed_words.iter().map(|w| normalize_word(w)).collect();
+
+        filter
+    }
+
+    ///
+    /// Builds a new filter from the "bad words" file and
+    /// the "allowlist" file.
+    ///
+    pub fn from_files(
+        bad_words_file_path: &str,
+        allowlist_file_path: &str,
+        min_substring_length: usize,
+    ) -> Self {
+        Self::new(
+            read_word_list(bad_words_file_path),
+            read_word_list(allowlist_file_path),
+            min_substring_length,
+        )
+    }
+}
+
+// ===============Instance Methods===============
+
+impl ProfanityFilter {
+    ///
+    /// Checks if a word is offensive, or if it is built
+    /// from an offensive word.
+    ///
+    pub fn is_profane(&self, word: &str) -> bool {
+        let word = normalize_word(word);
+
+        if self.allowlist.contains(&word) {
+            return false;
+        }
+
+        if self.blacklist.contains(&word) || self.inflections.contains(&word) {
+            return true;
+        }
+
+        let word_stem = get_stem(&word);
+        if word_length(&word_stem) >= MIN_STEM_LENGTH
+            && self.blacklist.contains(&word_stem)
+        {
+            return true;
+        }
+
+        self.substrings
+            .iter()
+            .any(|bad_word| word.contains(bad_word.as_str()))
+    }
+
+    ///
+    /// Scans every row and every column of a serialized grid
+    /// (Ex: `"abc_de#_ghi"`) for offensive words. Words can end up
+    /// next to each other on the board, so the letters of a row or
+    /// a column could spell something that no single placed word does.
+    ///
+    /// Every run of letters is checked with `is_profane()`. Every part
+    /// of a run is also checked against the bad words (and their
+    /// inflections), no matter how short, unless that part is within
+    /// a single placed word. Ex: "gas" and "sip" side by side spell
+    /// "ass", but "glass" on its own is fine, since the placed words
+    /// were already checked when the clean dictionary was built.
+    ///
+    /// Returns each run of letters that was found to be offensive.
+    ///
+    pub fn find_profanity_in_grid(
+        &self,
+        grid: &str,
+        used: &WordLocationsOnBoard,
+    ) -> Vec<String> {
+        let rows: Vec<Vec<char>> =
+            grid.split('_').map(|row| row.chars().collect()).collect();
+        let number_of_columns =
+            rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        // Which placed word (if any) covers each cell, in each direction
+        let mut horizontal_words: HashMap<(usize, usize), usize> =
+            HashMap::new();
+        let mut vertical_words: HashMap<(usize, usize), usize> = HashMap::new();
+        for (word_index, (word, location)) in used.iter().enumerate() {
+            for offset in 0..word_length(word) {
+                match location.direction {
+                    Direction::H => horizontal_words.insert(
+                        (location.row, location.col + offset),
+                        word_index,
+                    ),
+                    Direction::V => vertical_words.insert(
+                        (location.row + offset, location.col),
+                        word_index,
+                    ),
+                };
+            }
+        }
+
+        // Each line is its letters, and the word covering each letter.
+        // Columns are read from top to bottom.
+        let lines = (0..rows.len())
+            .map(|row| {
+                (0..rows[row].len())
+                    .map(|col| {
+                        let word = horizontal_words.get(&(row, col)).copied();
+                        (rows[row][col], word)
+                    })
+                    .collect::<Vec<(char, Option<usize>)>>()
+            })
+            .chain((0..number_of_columns).map(|col| {
+                (0..rows.len())
+                    .filter(|row| col < rows[*row].len())
+                    .map(|row| {
+                        let word = vertical_words.get(&(row, col)).copied();
+                        (rows[row][col], word)
+                    })
+                    .collect()
+            }));
+
+        lines
+            .flat_map(|line| {
+                line.split(|(letter, _)| *letter == EMPTY_CELL_VALUE)
+                    .filter(|run| run.len() > 1)
+                    .filter(|run| self.is_profane_run(run))
+                    .map(|run| run.iter().map(|(letter, _)| letter).collect())
+                    .collect::<Vec<String>>()
+            })
+            .collect()
+    }
+
+    ///
+    /// Checks a run of letters (and the word covering each letter)
+    /// from a row or column of a grid. See `find_profanity_in_grid()`.
+    ///
+    fn is_profane_run(&self, run: &[(char, Option<usize>)]) -> bool {
+        let letters: Vec<char> =
+            run.iter().map(|(letter, _)| *letter).collect();
+        let whole_run: String = letters.iter().collect();
+        if self.allowlist.contains(&whole_run) {
+            return false;
+        }
+        if self.is_profane(&whole_run) {
+            return true;
+        }
+
+        (0..run.len()).any(|start| {
+            (start + 2..=run.len()).any(|end| {
+                let is_within_one_word = run[start..end]
+                    .iter()
+                    .all(|(_, word)| word.is_some() && *word == run[start].1);
+                let part: String = letters[start..end].iter().collect();
+                !is_within_one_word
+                    && (self.blacklist.contains(&part)
+                        || self.inflections.contains(&part))
+            })
+        })
+    }
+}
+
+// ===============Word Utilities===============
+
+fn normalize_word(word: &str) -> String {
+    word.trim().to_lowercase()
+}
+
+///
+/// Reads a word list file (one word per line) into a Vector
+///
+fn read_word_list(filepath: &str) -> Vec<String> {
+    let word_list_file = File::open(Path::new(filepath))
+        .unwrap_or_else(|_| panic!("Could not locate '{filepath}'!"));
+
+    BufReader::new(word_list_file)
+        .lines()
+        .map(|l| l.expect("Could not parse a line of the word list."))
+        .filter(|word| !word.trim().is_empty())
+        .collect()
+}
+
+///
+/// Strips the most common English inflection from a word.
+///
+/// Example:
+/// ```
+/// get_stem("jerking"); // "jerk"
+/// get_stem("puppies"); // "puppy"
+/// get_stem("jerk"); // "jerk"
+/// ```
+///
+fn get_stem(word: &str) -> String {
+    for suffix in INFLECTION_SUFFIXES {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if word_length(base) < 3 {
+                continue;
+            }
+            // "ponies" -> "pony"
+            if suffix == "ies" || suffix == "ied" {
+                return format!("{base}y");
+            }
+            // "clubbing" -> "club"
+            return undouble_final_letter(base);
+        }
+    }
+    word.to_owned()
+}
+
+///
+/// Builds the most common English inflections of a (root) word.
+///
+/// Example:
+/// ```
+/// // ["jerk", "jerks", "jerkes", "jerked", "jerking", ...]
+/// get_inflections("jerk");
+/// ```
+///
+fn get_inflections(word: &str) -> Vec<String> {
+    let mut inflections = vec![word.to_owned()];
+
+    let suffixes = ["s", "es", "ed", "ing"];
+    inflections.extend(suffixes.iter().map(|s| format!("{word}{s}")));
+
+    // Short words create too many false positives when inflected.
+    // Ex: "bod" -> "boding"
+    if word_length(word) < MIN_STEM_LENGTH {
+        return inflections;
+    }
+
+    // "rape" -> "raped", "raping"
+    if let Some(base) = word.strip_suffix('e') {
+        inflections.push(format!("{word}d"));
+        inflections.push(format!("{base}ing"));
+    }
+
+    // "pussy" -> "pussies"
+    if let Some(base) = word.strip_suffix('y') {
+        inflections.push(format!("{base}ies"));
+        inflections.push(format!("{base}ied"));
+    }
+
+    // "club" -> "clubbed", "clubbing"
+    if let Some(last_letter) = word.chars().last() {
+        if !is_vowel(last_letter) {
+            inflections.push(format!("{word}{last_letter}ed"));
+            inflections.push(format!("{word}{last_letter}ing"));
+        }
+    }
+
+    inflections
+}
+
+///
+/// "clubb" -> "club", but leaves "miss" & "bell" alone.
+///
+fn undouble_final_letter(word: &str) -> String {
+    let letters: Vec<char> = word.chars().collect();
+    match letters.as_slice() {
+        [.., second_last, last]
+            if second_last == last
+                && !is_vowel(*last)
+                && !['l', 's', 'z'].contains(last) =>
+        {
+            letters[..letters.len() - 1].iter().collect()
+        }
+        _ => word.to_owned(),
+    }
+}
+
+fn is_vowel(letter: char) -> bool {
+    matches!(letter, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_used;
+
+    fn make_filter() -> ProfanityFilter {
+        ProfanityFilter::new(
+            vec!["jerk".into(), "asses".into(), "rape".into()],
+            vec!["glasses".into()],
+            5,
+        )
+    }
+
+    #[test]
+    fn test_inflections_and_stems() {
+        let filter = make_filter();
+
+        assert!(filter.is_profane("jerk"));
+        assert!(filter.is_profane("jerks"));
+        assert!(filter.is_profane("jerking"));
+        assert!(filter.is_profane("raped"));
+        assert!(!filter.is_profane("rapped"));
+        assert!(!filter.is_profane("jersey"));
+    }
+
+    #[test]
+    fn test_substrings_and_allowlist() {
+        let filter = make_filter();
+
+        assert!(filter.is_profane("classes"));
+        assert!(!filter.is_profane("glasses"));
+    }
+
+    #[test]
+    fn test_find_profanity_in_grid() {
+        let filter = make_filter();
+
+        let clean_grid = "#j##_mess_#r##_####";
+        let clean_used = make_used(&[("mess", 1, 0, Direction::H)]);
+        let vertical_grid = "#j##_#e##_#r##_#k##";
+        let vertical_used = make_used(&[("jerk", 0, 1, Direction::V)]);
+        let horizontal_grid = "####_jerk_####_####";
+        let horizontal_used = make_used(&[("jerk", 1, 0, Direction::H)]);
+
+        assert!(filter
+            .find_profanity_in_grid(clean_grid, &clean_used)
+            .is_empty());
+        assert_eq!(
+            filter.find_profanity_in_grid(vertical_grid, &vertical_used),
+            vec!["jerk"]
+        );
+        assert_eq!(
+            filter.find_profanity_in_grid(horizontal_grid, &horizontal_used),
+            vec!["jerk"]
+        );
+    }
+
+    #[test]
+    fn test_find_short_profanity_across_words() {
+        // "ass" is too short to be searched for within words
+        let filter = ProfanityFilter::new(vec!["ass".into()], Vec::new(), 5);
+
+        let glass_grid = "glass_#####_#####_#####_#####";
+        let glass_used = make_used(&[("glass", 0, 0, Direction::H)]);
+        let gas_sip_grid = "gassip_######_######_######_######_######";
+        let gas_sip_used = make_used(&[
+            ("gas", 0, 0, Direction::H),
+            ("sip", 0, 3, Direction::H),
+        ]);
+
+        assert!(filter
+            .find_profanity_in_grid(glass_grid, &glass_used)
+            .is_empty());
+        assert_eq!(
+            filter.find_profanity_in_grid(gas_sip_grid, &gas_sip_used),
+            vec!["gassip"]
+        );
+    }
+}
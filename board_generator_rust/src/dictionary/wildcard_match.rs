@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+/// A wildcard, which can stand in for any letter. Ex: A blank tile
+/// of the wheel, or an unknown letter of a pattern (`"m??s"`)
+pub const WILDCARD_LETTER: char = '?';
+
+///
+/// A word that can be built from a set of letters which
+/// includes wildcards (blank tiles).
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WildcardMatch {
+    pub word: String,
+    /// The letters that the wildcards had to become to build the word,
+    /// in alphabetical order. Empty if the word didn't need any of
+    /// the wildcards. A word doesn't have to use every wildcard.
+    pub wildcard_letters: Vec<char>,
+}
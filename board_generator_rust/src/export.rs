@@ -0,0 +1,5 @@
+mod kriss_kross;
+mod puzzle_book;
+
+pub use kriss_kross::*;
+pub use puzzle_book::*;
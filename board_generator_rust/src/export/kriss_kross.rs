@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::board::{BoardError, BoardManager, EMPTY_CELL_VALUE};
+use crate::dictionary::WILDCARD_LETTER;
+use crate::generator::GeneratedBoard;
+
+///
+/// A fill-in puzzle. The player gets the blank grid and every
+/// word (grouped by length), and has to work out where each
+/// word goes from the lengths and the crossings.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KrissKrossPuzzle {
+    // The grid, with every letter replaced by `WILDCARD_LETTER`
+    pub grid: String,
+    // Every word of the puzzle, by its length, alphabetically
+    pub words_by_length: BTreeMap<usize, Vec<String>>,
+    // The completed grid
+    pub solution: String,
+}
+
+///
+/// Why a board can't be turned into a kriss-kross.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KrissKrossError {
+    /// The board's grid doesn't agree with its words
+    InvalidBoard(BoardError),
+    /// The words also fit the grid another way. Each pair is the
+    /// word of the board, and the word which could take its place.
+    NotUnique {
+        swapped_words: Vec<(String, String)>,
+    },
+}
+
+impl fmt::Display for KrissKrossError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KrissKrossError::InvalidBoard(error) => {
+                write!(f, "the board is not valid: {error}")
+            }
+            KrissKrossError::NotUnique { swapped_words } => {
+                let swaps = swapped_words
+                    .iter()
+                    .map(|(word, other)| format!("{word} -> {other}"))
+                    .join(", ");
+                write!(f, "the fill is not unique ({swaps})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KrissKrossError {}
+
+///
+/// The cells (indexes into the flat grid) of one word of the board.
+///
+struct Slot {
+    word: String,
+    cells: Vec<usize>,
+}
+
+///
+/// Turns a generated board into a kriss-kross puzzle. The layout
+/// is kept, but the player gets the word list instead of a wheel.
+///
+/// The puzzle is rejected if its fill isn't unique. That is, if the
+/// words can be arranged into the grid in any other way that agrees
+/// with every crossing. Ex: two 4-letter words which both start on
+/// an 's' of the same word could swap places.
+///
+/// Example:
+/// ```
+/// let boards = read_generated_boards("./boards.json");
+/// // Ex: { 4: ["rest", "seat"], 5: ["stare"] }
+/// export_kriss_kross(&boards[0])?.words_by_length;
+/// ```
+///
+pub fn export_kriss_kross(
+    board: &GeneratedBoard,
+) -> Result<KrissKrossPuzzle, KrissKrossError> {
+    let bm = BoardManager::from_generated_board(board)
+        .map_err(KrissKrossError::InvalidBoard)?;
+    let dimensions = bm.dimensions;
+
+    let slots: Vec<Slot> = bm
+        .get_used_word_objects()
+        .into_iter()
+        .sorted_by(|a, b| a.word.cmp(&b.word))
+        .map(|placed_word| Slot {
+            cells: placed_word
+                .cells
+                .iter()
+                .map(|cell| cell.row * dimensions + cell.col)
+                .collect(),
+            word: placed_word.word,
+        })
+        .collect();
+
+    let other_fills = find_fills(&slots, dimensions * dimensions, 2);
+    if let Some(other_fill) = other_fills
+        .iter()
+        .find(|fill| fill.iter().zip(slots.iter()).any(|(w, s)| **w != s.word))
+    {
+        let swapped_words = slots
+            .iter()
+            .zip(other_fill.iter())
+            .filter(|(slot, word)| slot.word != **word)
+            .map(|(slot, word)| (slot.word.to_owned(), word.to_string()))
+            .collect();
+        return Err(KrissKrossError::NotUnique { swapped_words });
+    }
+
+    let grid = board
+        .grid
+        .chars()
+        .map(|letter| match letter {
+            '_' | EMPTY_CELL_VALUE => letter,
+            _ => WILDCARD_LETTER,
+        })
+        .collect();
+    let mut words_by_length: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for slot in slots.iter() {
+        words_by_length
+            .entry(slot.cells.len())
+            .or_default()
+            .push(slot.word.to_owned());
+    }
+
+    Ok(KrissKrossPuzzle {
+        grid,
+        words_by_length,
+        solution: board.grid.to_owned(),
+    })
+}
+
+///
+/// Solves the puzzle the way the player would, without knowing
+/// which word goes where. Returns up to `max_fills` fills, each
+/// being the word in each slot.
+///
+fn find_fills(
+    slots: &[Slot],
+    number_of_cells: usize,
+    max_fills: usize,
+) -> Vec<Vec<&str>> {
+    let mut fills = Vec::new();
+    let mut grid: Vec<Option<char>> = vec![None; number_of_cells];
+    let mut fill: Vec<Option<&str>> = vec![None; slots.len()];
+    let mut used_words: HashSet<&str> = HashSet::new();
+    fill_next_slot(
+        slots,
+        &mut grid,
+        &mut fill,
+        &mut used_words,
+        &mut fills,
+        max_fills,
+    );
+    fills
+}
+
+fn fill_next_slot<'a>(
+    slots: &'a [Slot],
+    grid: &mut [Option<char>],
+    fill: &mut [Option<&'a str>],
+    used_words: &mut HashSet<&'a str>,
+    fills: &mut Vec<Vec<&'a str>>,
+    max_fills: usize,
+) {
+    if fills.len() >= max_fills {
+        return;
+    }
+
+    // The empty slot with the fewest words that fit, is the most
+    // likely to rule out a wrong guess early
+    let candidates_by_slot = (0..slots.len())
+        .filter(|index| fill[*index].is_none())
+        .map(|index| {
+            let candidates: Vec<&str> = slots
+                .iter()
+                .map(|slot| slot.word.as_str())
+                .filter(|word| !used_words.contains(word))
+                .filter(|word| fits_slot(word, &slots[index], grid))
+                .collect();
+            (index, candidates)
+        })
+        .min_by_key(|(_, candidates)| candidates.len());
+
+    let Some((index, candidates)) = candidates_by_slot else {
+        // Every slot has a word
+        fills.push(fill.iter().map(|word| word.unwrap_or_default()).collect());
+        return;
+    };
+
+    for word in candidates {
+        let previous_letters: Vec<Option<char>> =
+            slots[index].cells.iter().map(|cell| grid[*cell]).collect();
+        for (cell, letter) in slots[index].cells.iter().zip(word.chars()) {
+            grid[*cell] = Some(letter);
+        }
+        fill[index] = Some(word);
+        used_words.insert(word);
+
+        fill_next_slot(slots, grid, fill, used_words, fills, max_fills);
+
+        used_words.remove(word);
+        fill[index] = None;
+        for (cell, letter) in slots[index].cells.iter().zip(previous_letters) {
+            grid[*cell] = letter;
+        }
+    }
+}
+
+fn fits_slot(word: &str, slot: &Slot, grid: &[Option<char>]) -> bool {
+    word.chars().count() == slot.cells.len()
+        && slot
+            .cells
+            .iter()
+            .zip(word.chars())
+            .all(|(cell, letter)| grid[*cell].is_none_or(|l| l == letter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::test_utils::make_board;
+
+    #[test]
+    fn test_export_kriss_kross() {
+        let board = make_board(
+            5,
+            &[
+                ("tests", 0, 0, Direction::H),
+                ("tea", 0, 0, Direction::V),
+                ("sat", 0, 2, Direction::V),
+            ],
+        );
+
+        let puzzle = export_kriss_kross(&board).unwrap();
+
+        assert_eq!(puzzle.grid, "?????_?#?##_?#?##_#####_#####");
+        assert_eq!(puzzle.solution, board.grid);
+        assert_eq!(
+            puzzle.words_by_length,
+            BTreeMap::from([
+                (3, vec![String::from("sat"), String::from("tea")]),
+                (5, vec![String::from("tests")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_reject_swappable_words() {
+        // Both 3-letter words start on a 't' of "tests"
+        let board = make_board(
+            5,
+            &[
+                ("tests", 0, 0, Direction::H),
+                ("tea", 0, 0, Direction::V),
+                ("toe", 0, 3, Direction::V),
+            ],
+        );
+
+        assert_eq!(
+            export_kriss_kross(&board),
+            Err(KrissKrossError::NotUnique {
+                swapped_words: vec![
+                    (String::from("tea"), String::from("toe")),
+                    (String::from("toe"), String::from("tea")),
+                ]
+            })
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::board::Direction;
+use crate::config::{ExportConfig, RenderConfig};
+use crate::generator::GeneratedBoard;
+use crate::render::{render_board_svg, BoardLayout, SvgView};
+use crate::utils::{escape_html, word_length};
+
+/// Styles for the printed pages. Each `.page` is printed on its own sheet.
+const PUZZLE_BOOK_CSS: &str = r#"
+@page { size: letter; margin: 0.5in; }
+body { font-family: Arial, sans-serif; margin: 0; color: #0f0f0f; }
+h1 { text-align: center; }
+.page { page-break-after: always; break-after: page; display: flex; flex-direction: column; gap: 0.25in; }
+.page:last-child { page-break-after: auto; break-after: auto; }
+.puzzle { display: flex; gap: 0.25in; align-items: flex-start; break-inside: avoid; }
+.puzzle svg { width: 60%; height: auto; flex-shrink: 0; }
+.clues { font-size: 11pt; }
+.clues h3 { margin: 0.1in 0 0.05in 0; }
+.clues ol { margin: 0; padding-left: 0; list-style: none; }
+.answers { display: grid; grid-template-columns: 1fr 1fr; gap: 0.2in; }
+.answers svg { width: 100%; height: auto; }
+.answers p { font-size: 9pt; margin: 0; }
+"#;
+
+///
+/// Lays out boards as a printable puzzle booklet. The booklet is a single
+/// self-contained HTML document (the grids are inline SVG), which can be
+/// printed, or saved as a PDF, from any browser.
+///
+/// Each page holds `boards_per_page` puzzles. Each puzzle has a blank
+/// (numbered) grid, the wheel letters, and a clue for each word giving
+/// only its length. The answer key is at the back of the booklet.
+///
+pub fn create_puzzle_book(
+    boards: &[GeneratedBoard],
+    export_config: &ExportConfig,
+    render_config: &RenderConfig,
+) -> String {
+    // Printed pages look better with a white background
+    let render_config = RenderConfig {
+        background_color: String::from("#ffffff"),
+        ..render_config.clone()
+    };
+    let boards_per_page = export_config.boards_per_page.max(1);
+    let title = escape_html(&export_config.book_title);
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, r#"<html lang="en"><head><meta charset="utf-8">"#);
+    let _ = writeln!(html, "<title>{title}</title>");
+    let _ = writeln!(html, "<style>{PUZZLE_BOOK_CSS}</style></head><body>");
+
+    // ===============The Puzzles===============
+    for (page_index, page_boards) in boards.chunks(boards_per_page).enumerate()
+    {
+        let _ = writeln!(html, r#"<section class="page">"#);
+        if page_index == 0 {
+            let _ = writeln!(html, "<h1>{title}</h1>");
+        }
+        for (index, board) in page_boards.iter().enumerate() {
+            let puzzle_number = page_index * boards_per_page + index + 1;
+            let _ = writeln!(html, r#"<div class="puzzle">"#);
+            html.push_str(&render_board_svg(
+                board,
+                SvgView::Puzzle,
+                &render_config,
+            ));
+            let _ = writeln!(html, r#"<div class="clues">"#);
+            let _ = writeln!(html, "<h2>Puzzle {puzzle_number}</h2>");
+            html.push_str(&get_clues_html(board));
+            let _ = writeln!(html, "</div></div>");
+        }
+        let _ = writeln!(html, "</section>");
+    }
+
+    // ===============The Answer Key===============
+    // The answers are smaller, so twice as many fit on a page
+    for (page_index, page_boards) in
+        boards.chunks(boards_per_page * 2).enumerate()
+    {
+        let _ = writeln!(html, r#"<section class="page">"#);
+        if page_index == 0 {
+            let _ = writeln!(html, "<h1>Answers</h1>");
+        }
+        let _ = writeln!(html, r#"<div class="answers">"#);
+        for (index, board) in page_boards.iter().enumerate() {
+            let puzzle_number = page_index * boards_per_page * 2 + index + 1;
+            let _ = writeln!(html, "<div><h3>Puzzle {puzzle_number}</h3>");
+            html.push_str(&render_board_svg(
+                board,
+                SvgView::Annotated,
+                &render_config,
+            ));
+            let answers: Vec<String> = BoardLayout::new(board)
+                .words
+                .iter()
+                .map(|(number, direction, word)| {
+                    format!(
+                        "{number} {}: {}",
+                        direction_name(*direction),
+                        escape_html(&word.to_uppercase())
+                    )
+                })
+                .collect();
+            let _ = writeln!(html, "<p>{}</p></div>", answers.join(", "));
+        }
+        let _ = writeln!(html, "</div></section>");
+    }
+
+    let _ = writeln!(html, "</body></html>");
+    html
+}
+
+///
+/// The clues of a puzzle, grouped by direction. Ex: "1. (7 letters)"
+///
+fn get_clues_html(board: &GeneratedBoard) -> String {
+    let layout = BoardLayout::new(board);
+    let mut html = String::new();
+
+    // Sorted, so the letters don't spell out the starting word
+    let letters: String = board.letters.chars().sorted().collect();
+    let _ = writeln!(
+        html,
+        "<p>Letters: <strong>{}</strong></p>",
+        escape_html(&letters.to_uppercase())
+    );
+    for direction in [Direction::H, Direction::V] {
+        let _ = writeln!(html, "<h3>{}</h3><ol>", direction_name(direction));
+        let mut clues: Vec<(usize, usize)> = layout
+            .words
+            .iter()
+            .filter(|(_, word_direction, _)| *word_direction == direction)
+            .map(|(number, _, word)| (*number, word_length(word)))
+            .collect();
+        clues.sort();
+        for (number, number_of_letters) in clues {
+            let _ = writeln!(
+                html,
+                "<li>{number}. ({number_of_letters} letters)</li>"
+            );
+        }
+        let _ = writeln!(html, "</ol>");
+    }
+
+    html
+}
+
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::H => "Across",
+        Direction::V => "Down",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_utils::make_misuses_board;
+
+    #[test]
+    fn test_create_puzzle_book() {
+        let board = make_misuses_board();
+        let config = Config::default();
+        let export_config = ExportConfig {
+            boards_per_page: 2,
+            ..config.export
+        };
+
+        let html =
+            create_puzzle_book(&vec![board; 3], &export_config, &config.render);
+
+        // 2 pages of puzzles, and 1 page of answers
+        assert_eq!(html.matches(r#"<section class="page">"#).count(), 3);
+        assert_eq!(html.matches("<svg").count(), 6);
+        assert_eq!(html.matches("Letters: <strong>EIMSSSU").count(), 3);
+        assert_eq!(html.matches("<li>1. (7 letters)</li>").count(), 3);
+        assert_eq!(html.matches("<li>1. (4 letters)</li>").count(), 3);
+        assert_eq!(html.matches("1 Across: MISUSES, 1 Down: MESS").count(), 3);
+    }
+}
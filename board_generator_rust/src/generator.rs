@@ -1,7 +1,19 @@
+mod daily_puzzle;
+mod densify_board;
 mod generate_board;
 mod generated_board;
+mod remix_board;
+mod weekend_board;
 
-pub use generate_board::try_to_generate_a_board;
+pub use daily_puzzle::generate_daily_puzzles;
+pub use densify_board::{densify_board, DensityReport};
+pub use generate_board::{
+    try_to_generate_a_board, try_to_generate_a_board_from,
+    try_to_generate_a_board_with_anchors,
+};
 pub use generated_board::{
-    GeneratedBoard, WordLocationOnBoard, WordLocationsOnBoard,
+    read_generated_boards, GeneratedBoard, WordLocationOnBoard,
+    WordLocationsOnBoard,
 };
+pub use remix_board::{remix_board, transpose_board};
+pub use weekend_board::try_to_generate_a_weekend_board;
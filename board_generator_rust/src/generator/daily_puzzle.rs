@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{Days, NaiveDate};
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::config::Config;
+use crate::dictionary::DictionaryManager;
+use crate::utils::set_random_seed;
+
+use super::{try_to_generate_a_board_from, GeneratedBoard};
+
+///
+/// Generates the daily puzzle of each day from `first_date`, for
+/// `number_of_days` days. Every player gets the same board for the same
+/// day, without a server, since everything random about a daily puzzle
+/// comes from a seed built from the date and the (secret) salt.
+///
+/// - The result only changes if the dictionary or config changes.
+/// - The same starting word isn't used twice within `repeat_window` days.
+/// - Daily puzzles are never themed.
+///
+/// Days before the config's `epoch` have no puzzle, and neither do days
+/// whose starting word couldn't build a board (which is rare). Without
+/// a salt, there are no puzzles at all.
+///
+/// Example:
+/// ```
+/// // { "2026-10-19": GeneratedBoard, "2026-10-20": GeneratedBoard }
+/// generate_daily_puzzles(&dictionary, &all_config, date, 2);
+/// ```
+///
+pub fn generate_daily_puzzles(
+    dictionary: &DictionaryManager,
+    all_config: &Config,
+    first_date: NaiveDate,
+    number_of_days: usize,
+) -> BTreeMap<String, GeneratedBoard> {
+    let daily_config = &all_config.daily;
+    let Some(salt) = &daily_config.salt else {
+        return BTreeMap::new();
+    };
+    let mut all_config = all_config.clone();
+    all_config.generator.theme = None;
+    all_config.generator.should_log_board_info = false;
+
+    let Some(last_date) = first_date
+        .checked_add_days(Days::new(number_of_days.saturating_sub(1) as u64))
+    else {
+        return BTreeMap::new();
+    };
+    // A starting word with barely enough words to reach the target
+    // could fail to build a board no matter the seed.
+    let starting_words: Vec<String> = dictionary
+        .get_starting_words()
+        .iter()
+        .filter(|starting_word| {
+            dictionary.get_starting_word(starting_word).is_some_and(
+                |(_, subwords)| {
+                    subwords.len() >= all_config.generator.max_words_to_place
+                },
+            )
+        })
+        .cloned()
+        .collect();
+    let starting_words = choose_daily_starting_words(
+        &starting_words,
+        salt,
+        daily_config.epoch,
+        last_date,
+        daily_config.repeat_window,
+    );
+
+    let mut daily_puzzles = BTreeMap::new();
+    for (date, starting_word) in starting_words {
+        if date < first_date {
+            continue;
+        }
+        let Some((starting_word, subwords)) =
+            dictionary.get_starting_word(&starting_word)
+        else {
+            continue;
+        };
+
+        // Each attempt has its own seed, so a failed attempt
+        // (and the next attempt) is repeatable as well.
+        let board =
+            (0..daily_config.max_attempts_per_day).find_map(|attempt| {
+                set_random_seed(Some(get_daily_seed(date, salt, attempt)));
+                try_to_generate_a_board_from(
+                    dictionary,
+                    &all_config,
+                    starting_word,
+                    subwords,
+                )
+            });
+        set_random_seed(None);
+
+        if let Some(board) = board {
+            daily_puzzles.insert(date.to_string(), board);
+        }
+    }
+
+    daily_puzzles
+}
+
+///
+/// Chooses the starting word of every day from `epoch` until
+/// `last_date`. Each day's choice depends on the days before it (to
+/// avoid repeats), which is why they're always chosen from the epoch.
+///
+fn choose_daily_starting_words(
+    starting_words: &[String],
+    salt: &str,
+    epoch: NaiveDate,
+    last_date: NaiveDate,
+    repeat_window: usize,
+) -> Vec<(NaiveDate, String)> {
+    if starting_words.is_empty() {
+        return Vec::new();
+    }
+    // A window bigger than the dictionary can't be satisfied
+    let repeat_window =
+        repeat_window.min(starting_words.len().saturating_sub(1));
+    let mut recent_words: VecDeque<&String> = VecDeque::new();
+    let mut chosen_words = Vec::new();
+
+    for date in epoch.iter_days().take_while(|date| *date <= last_date) {
+        let mut rng = ChaCha8Rng::seed_from_u64(get_daily_seed(date, salt, 0));
+        let Some(starting_word) =
+            std::iter::repeat_with(|| starting_words.choose(&mut rng))
+                .flatten()
+                .find(|word| !recent_words.contains(word))
+        else {
+            break;
+        };
+
+        recent_words.push_back(starting_word);
+        if recent_words.len() > repeat_window {
+            recent_words.pop_front();
+        }
+        chosen_words.push((date, starting_word.to_owned()));
+    }
+
+    chosen_words
+}
+
+///
+/// Builds the seed of a day's puzzle. This is the (64 bit) FNV-1a hash
+/// of the salt, date and attempt, since it must never change between
+/// versions of Rust (unlike the standard library's hasher).
+///
+fn get_daily_seed(date: NaiveDate, salt: &str, attempt: usize) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    format!("{salt}:{date}:{attempt}")
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn test_choose_daily_starting_words() {
+        let starting_words: Vec<String> = ["aaa", "bbb", "ccc", "ddd", "eee"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        let choose = |salt| {
+            choose_daily_starting_words(
+                &starting_words,
+                salt,
+                date("2026-01-01"),
+                date("2026-03-01"),
+                3,
+            )
+        };
+
+        let chosen_words = choose("salt");
+        assert_eq!(chosen_words.len(), 60);
+        assert_eq!(chosen_words, choose("salt"));
+        assert_ne!(chosen_words, choose("pepper"));
+        assert!(choose_daily_starting_words(
+            &[],
+            "salt",
+            date("2026-01-01"),
+            date("2026-03-01"),
+            3
+        )
+        .is_empty());
+        for window in chosen_words.windows(4) {
+            let words: Vec<&String> = window.iter().map(|(_, w)| w).collect();
+            assert!(!words[1..].contains(&words[0]), "{words:?}");
+        }
+    }
+
+    #[test]
+    fn test_generate_daily_puzzles() {
+        let mut dictionary = DictionaryManager::new();
+        dictionary.load_precomputed_dictionary();
+        let mut all_config = Config::default();
+        all_config.daily.salt = Some("salt".to_owned());
+
+        let first_run = generate_daily_puzzles(
+            &dictionary,
+            &all_config,
+            date("2026-10-19"),
+            2,
+        );
+        let second_run = generate_daily_puzzles(
+            &dictionary,
+            &all_config,
+            date("2026-10-20"),
+            1,
+        );
+
+        let third_run = generate_daily_puzzles(
+            &dictionary,
+            &all_config,
+            date("2026-10-19"),
+            2,
+        );
+
+        assert_eq!(first_run.len(), 2);
+        assert_eq!(
+            serde_json::to_string(&first_run).unwrap(),
+            serde_json::to_string(&third_run).unwrap()
+        );
+        assert_eq!(first_run["2026-10-20"], second_run["2026-10-20"]);
+        assert_ne!(first_run["2026-10-19"], first_run["2026-10-20"]);
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use crate::board::{BoardManager, Direction, PossibleWordLocation};
+use crate::utils::word_length;
+
+/// How many locations are compared for each candidate word
+const MAX_LOCATIONS_PER_WORD: usize = 20;
+
+///
+/// How much denser a board became after the densification pass.
+///
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct DensityReport {
+    /// The number of words on the board before the pass
+    pub words_before: usize,
+    pub words_added: usize,
+    /// New cells which are used by two words
+    pub crossings_added: usize,
+    pub filled_cells_before: usize,
+    pub filled_cells_after: usize,
+}
+
+///
+/// Once a board has reached its word target, it often still has
+/// gaps where more of the wheel's words could fit. This tries to
+/// place the remaining candidate words into the finished grid,
+/// until the board has `max_words` words or nothing else fits.
+///
+/// The words are placed with the same adjacency rules as the rest
+/// of the board. Each round, the placement which crosses the most
+/// letters already on the board wins, so the board gets more
+/// interlocked rather than just bigger.
+///
+/// Example:
+/// ```
+/// let report = densify_board(&mut bm, &all_possible_words, 24);
+/// // Ex: 3
+/// report.words_added;
+/// ```
+///
+pub fn densify_board(
+    bm: &mut BoardManager,
+    candidate_words: &[String],
+    max_words: usize,
+) -> DensityReport {
+    let words_before = bm.get_number_of_used_words();
+    let crossings_before = bm.get_number_of_crossings();
+    let filled_cells_before = bm.get_number_of_filled_cells();
+
+    while bm.get_number_of_used_words() < max_words {
+        let unplaced_words: Vec<&String> = candidate_words
+            .iter()
+            .filter(|word| !bm.is_word_placed(word))
+            .collect();
+        let locations: Vec<PossibleWordLocation> = unplaced_words
+            .into_iter()
+            .flat_map(|word| {
+                bm.find_some_locations_for_word(word, MAX_LOCATIONS_PER_WORD)
+            })
+            .collect();
+
+        // The first of the best locations is kept, so that
+        // a seeded board is always densified the same way
+        let best_location = locations
+            .into_iter()
+            .map(|location| (count_crossings(bm, &location), location))
+            .fold(None, |best, (crossings, location)| match best {
+                Some((best_crossings, _)) if best_crossings >= crossings => {
+                    best
+                }
+                _ => Some((crossings, location)),
+            });
+
+        let Some((_, location)) = best_location else {
+            break;
+        };
+        bm.place_word(
+            &location.word,
+            location.start_row,
+            location.start_col,
+            location.direction,
+        );
+    }
+
+    DensityReport {
+        words_before,
+        words_added: bm.get_number_of_used_words() - words_before,
+        crossings_added: bm.get_number_of_crossings() - crossings_before,
+        filled_cells_before,
+        filled_cells_after: bm.get_number_of_filled_cells(),
+    }
+}
+
+///
+/// How many letters, already on the board, the word
+/// would cross if it were placed at the location.
+///
+fn count_crossings(
+    bm: &BoardManager,
+    location: &PossibleWordLocation,
+) -> usize {
+    (0..word_length(&location.word))
+        .map(|offset| match location.direction {
+            Direction::H => (location.start_row, location.start_col + offset),
+            Direction::V => (location.start_row + offset, location.start_col),
+        })
+        .filter(|(row, col)| bm.cell_is_used(*row, *col))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_densify_board() {
+        let mut bm = BoardManager::new();
+        bm.new_list(9);
+        bm.place_word("stare", 4, 2, Direction::H);
+        bm.place_word("sea", 4, 2, Direction::V);
+
+        let candidates: Vec<String> = ["rest", "tears", "east", "art"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        let report = densify_board(&mut bm, &candidates, 4);
+
+        assert_eq!(report.words_before, 2);
+        assert_eq!(report.words_added, 2);
+        assert_eq!(bm.get_number_of_used_words(), 4);
+        assert!(report.crossings_added >= 2);
+        assert!(report.filled_cells_after > report.filled_cells_before);
+        assert_eq!(report.filled_cells_after, bm.get_number_of_filled_cells());
+    }
+
+    #[test]
+    fn test_densify_board_without_room() {
+        let mut bm = BoardManager::new();
+        bm.new_list(5);
+        bm.place_word("stare", 2, 0, Direction::H);
+
+        // There is no letter of "stare" which "lull" could cross
+        let candidates = vec![String::from("lull")];
+        let report = densify_board(&mut bm, &candidates, 10);
+
+        assert_eq!(
+            report,
+            DensityReport {
+                words_before: 1,
+                words_added: 0,
+                crossings_added: 0,
+                filled_cells_before: 5,
+                filled_cells_after: 5,
+            }
+        );
+    }
+}
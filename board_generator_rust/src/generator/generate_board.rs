@@ -1,11 +1,12 @@
+use crate::analysis::{find_ambiguous_slots, AmbiguityAction};
 use crate::board::{get_random_direction, BoardManager, Direction};
 use crate::config::Config;
-use crate::dictionary::DictionaryManager;
+use crate::dictionary::{DictionaryManager, WILDCARD_LETTER};
 use crate::utils::*;
 use itertools::Itertools;
-use std::cmp::min;
+use std::{cmp::min, collections::HashSet};
 
-use super::GeneratedBoard;
+use super::{densify_board, GeneratedBoard};
 
 ///
 /// This is sort of the "starting point" for generating a new board.
@@ -22,12 +23,124 @@ pub fn try_to_generate_a_board(
     // =====================================================
 
     // New Algorithm - Use the precomputed values
-    // let starting_tuple = dictionary.get_random_starting_word()?;
-    let orig_starting_tuple = dictionary.get_random_starting_word()?.to_owned();
-    let mut starting_tuple: (String, Vec<String>) = (orig_starting_tuple.0.to_owned(), orig_starting_tuple.1.to_owned());
-    starting_tuple.1.retain(|x| x == "mise" || x == "mises");
-    let random_seven_letter_word: String = starting_tuple.0.to_string();
-    let all_possible_words: Vec<String> = starting_tuple.1.to_owned();
+    // For a themed board, the starting word must be able to build
+    // enough of the theme's words.
+    let starting_tuple = match all_config.generator.theme.as_deref() {
+        Some(theme) => dictionary.get_random_starting_word_for_theme(
+            theme,
+            all_config.generator.min_themed_words,
+        )?,
+        None => dictionary.get_random_starting_word()?,
+    };
+
+    try_to_generate_a_board_from(
+        dictionary,
+        all_config,
+        starting_tuple.0,
+        starting_tuple.1,
+    )
+}
+
+///
+/// Same as `try_to_generate_a_board()`, but the starting word
+/// (and the words its letters can build) is chosen by the caller.
+///
+pub fn try_to_generate_a_board_from(
+    dictionary: &DictionaryManager,
+    all_config: &Config,
+    starting_word: &str,
+    starting_subwords: &[String],
+) -> Option<GeneratedBoard> {
+    try_to_generate_a_board_with_anchors(
+        dictionary,
+        all_config,
+        &[starting_word],
+        starting_word,
+        starting_subwords,
+    )
+}
+
+///
+/// Same as `try_to_generate_a_board_from()`, but the board starts out
+/// with several words (anchors) already placed. The first anchor is
+/// placed in the center, and every other anchor crosses the board
+/// before any other word is placed.
+///
+/// `letters` is the wheel, which must be able to build every anchor,
+/// and `subwords` are the words which can be placed around them.
+/// The board's `all` always includes the anchors. With several
+/// anchors, it's looked up from the whole wheel, so the dictionary
+/// must have its clean words loaded.
+///
+/// Example:
+/// ```
+/// // A 9-letter wheel, built from two crossing 7-letter words
+/// try_to_generate_a_board_with_anchors(
+///     &dictionary,
+///     &all_config,
+///     &["denmark", "admired"],
+///     "addeikmnr",
+///     &subwords,
+/// );
+/// ```
+///
+pub fn try_to_generate_a_board_with_anchors(
+    dictionary: &DictionaryManager,
+    all_config: &Config,
+    anchor_words: &[&str],
+    letters: &str,
+    subwords: &[String],
+) -> Option<GeneratedBoard> {
+    let theme = all_config.generator.theme.as_deref();
+    let (random_seven_letter_word, other_anchor_words) =
+        anchor_words.split_first()?;
+
+    // Wildcard tiles let the wheel build many more words than the
+    // starting word's letters alone. So those need to be looked up.
+    let wheel_letters =
+        add_wildcard_tiles(letters, all_config.generator.wildcard_tiles);
+    let all_possible_words: Vec<String> =
+        match all_config.generator.wildcard_tiles {
+            0 => subwords.to_owned(),
+            _ => dictionary
+                .find_all_words_for_letters_with_wildcards(&wheel_letters)
+                .into_iter()
+                .map(|wildcard_match| wildcard_match.word)
+                .filter(|word| !anchor_words.contains(&word.as_str()))
+                .collect(),
+        };
+    // Every word the player can make with the wheel, anchors included.
+    // The subwords only have the words of each anchor's own letters,
+    // and a wheel built from several anchors can make many more words
+    // than that.
+    let mut all_words: Vec<String> =
+        match (all_config.generator.wildcard_tiles, other_anchor_words) {
+            (0, [_, ..]) => {
+                dictionary.find_all_words_for_letters(letters, false)
+            }
+            _ => all_possible_words.clone(),
+        };
+    all_words.extend(anchor_words.iter().map(|word| word.to_string()));
+    all_words.sort();
+    all_words.dedup();
+
+    // All of the words (including the starting word) which belong
+    // to the theme. Without a theme, there are none.
+    let themed_words: HashSet<String> = match theme {
+        Some(theme) => {
+            let mut every_word = all_possible_words.clone();
+            every_word.extend(anchor_words.iter().map(|word| word.to_string()));
+            dictionary
+                .get_themed_words(theme, &every_word)
+                .into_iter()
+                .collect()
+        }
+        None => HashSet::new(),
+    };
+    let min_themed_words = match theme {
+        Some(_) => all_config.generator.min_themed_words,
+        None => 0,
+    };
 
     // =====================================================
     //  RANDOMIZE ALL POSSIBLE WORDS THAT CAN BE PLACED
@@ -35,8 +148,16 @@ pub fn try_to_generate_a_board(
 
     // Too many possible words makes the algorithm slow.
     // So, put a cap on the number of words added to the pool.
-    let initial_words_shuffled: Vec<String> = shuffle(&all_possible_words)
+    // Themed words go to the front of the pool so they're always
+    // considered, and so they get placed first.
+    let (pool_themed, pool_others): (Vec<String>, Vec<String>) =
+        all_possible_words
+            .iter()
+            .cloned()
+            .partition(|word| themed_words.contains(word));
+    let initial_words_shuffled: Vec<String> = shuffle(&pool_themed)
         .into_iter()
+        .chain(shuffle(&pool_others))
         .take(all_config.generator.max_words_to_consider)
         .collect();
 
@@ -49,13 +170,10 @@ pub fn try_to_generate_a_board(
     bm.new_list(all_config.generator.board_dimensions);
     let initial_direction = get_random_direction();
     let center_base = board_dimensions / 2;
+    let half_word_length = word_length(random_seven_letter_word) / 2;
     let (center_row, center_col) = match initial_direction {
-        Direction::H => {
-            (center_base, center_base - random_seven_letter_word.len() / 2)
-        }
-        Direction::V => {
-            (center_base - random_seven_letter_word.len() / 2, center_base)
-        }
+        Direction::H => (center_base, center_base - half_word_length),
+        Direction::V => (center_base - half_word_length, center_base),
     };
 
     // =======================================================
@@ -70,14 +188,13 @@ pub fn try_to_generate_a_board(
     // let desired_words_count = (initial_words_shuffled.len() as f32
     //     * get_random_float_in_range(0.50, 0.8))
     // .round();
-    let desired_words_count = initial_words_shuffled.len() + 1;
+    let desired_words_count = initial_words_shuffled.len() + anchor_words.len();
     // Set a maximum number of words to place on the board
     let capped_words_count: usize = get_random_int_in_range(
         all_config.generator.max_words_to_place - 3,
         all_config.generator.max_words_to_place,
     );
-    let minimum_words_count =
-        min(desired_words_count as usize, capped_words_count);
+    let minimum_words_count = min(desired_words_count, capped_words_count);
 
     // =====================================================
     //  BEGIN TRYING ALL POSSIBLE WORD COMBINATIONS UNTIL THE
@@ -106,6 +223,27 @@ pub fn try_to_generate_a_board(
     // to be considered "good enough".
     let mut succeeded = false;
 
+    // The board starts out with only the initial seven-letter word
+    // placed in the center (and any other anchors crossing it).
+    // Each attempt rolls back to this snapshot.
+    bm.place_word(
+        random_seven_letter_word,
+        center_row,
+        center_col,
+        initial_direction,
+    );
+    for anchor_word in other_anchor_words {
+        let anchor_location =
+            bm.find_some_locations_for_word(anchor_word, 1).pop()?;
+        bm.place_word(
+            anchor_word,
+            anchor_location.start_row,
+            anchor_location.start_col,
+            anchor_location.direction,
+        );
+    }
+    let starting_snapshot = bm.snapshot();
+
     // This loop contains most of the flow and algorithmic logic for
     // generating a board. It outlines the steps necessary to
     // make a board, while some of the other classes implement the specific
@@ -120,13 +258,8 @@ pub fn try_to_generate_a_board(
         // Every time we try out a new word-list (permutation), we need
         // to reset the board to the starting state. Which is an empty
         // board with only the initial seven-letter word placed in the center.
-        bm.clear_board();
-        bm.place_word(
-            &random_seven_letter_word,
-            center_row,
-            center_col,
-            initial_direction,
-        );
+        bm.restore(starting_snapshot)
+            .expect("The starting snapshot is never cleared.");
 
         // Iterate through each of the words in the words-list (one permutation),
         // then try to place it somewhere on the board (if there even is a valid
@@ -147,7 +280,7 @@ pub fn try_to_generate_a_board(
             //     bm.find_some_locations_for_word(word, 1);
             let locations_for_this_word =
                 bm.find_some_locations_for_word(word, 20);
-            
+
             // println!("Matches = {:#?}", locations_for_this_word);
 
             if !locations_for_this_word.is_empty() {
@@ -162,9 +295,12 @@ pub fn try_to_generate_a_board(
                     one_location.direction,
                 );
 
-                // We've been able to place enough words on the board using
-                // this permutation.
-                if bm.get_number_of_used_words() >= minimum_words_count {
+                // We've been able to place enough words (and enough themed
+                // words) on the board using this permutation.
+                if bm.get_number_of_used_words() >= minimum_words_count
+                    && count_themed_words(&bm, &themed_words)
+                        >= min_themed_words
+                {
                     succeeded = true;
                     break;
                 }
@@ -184,26 +320,232 @@ pub fn try_to_generate_a_board(
     if all_config.generator.should_log_board_info {
         println!("---RESULTS----");
         bm.debug();
-        println!("Randomly chosen word: '{random_seven_letter_word}'");
+        println!("Randomly chosen word: '{}'", anchor_words.join("', '"));
         println!(
             "Targeted {} out of {} selected words. ({} total)",
             minimum_words_count,
             initial_words_shuffled.len(),
             all_possible_words.len()
         );
+        if let Some(theme) = theme {
+            println!(
+                "Theme '{theme}': needed {min_themed_words} of {} themed words.",
+                themed_words.len()
+            );
+        }
         println!("Total Attempts: {attempts}");
         println!("Was successful? {succeeded}");
         println!("--------------");
     }
 
-    if succeeded {
-        Some(GeneratedBoard {
-            grid: bm.get_grid_str(),
-            used: bm.get_placed_words_for_serialization(),
-            all: all_possible_words.join("_"),
-            letters: random_seven_letter_word,
-        })
-    } else {
-        None
+    if !succeeded {
+        return None;
+    }
+
+    // ==========================
+    //  DENSIFICATION
+    // ==========================
+
+    // The board has enough words, but there are often gaps
+    // where more of the possible words could still fit.
+    let density = match all_config.generator.should_densify_boards {
+        true => Some(densify_board(
+            &mut bm,
+            &all_possible_words,
+            all_config.generator.max_words_after_densifying,
+        )),
+        false => None,
+    };
+    if let Some(density) = density {
+        if all_config.generator.should_log_board_info {
+            println!(
+                "Densified: +{} words, +{} crossings, {} -> {} cells",
+                density.words_added,
+                density.crossings_added,
+                density.filled_cells_before,
+                density.filled_cells_after
+            );
+        }
+    }
+
+    // ==========================
+    //  PROFANITY CHECK
+    // ==========================
+
+    // Every placed word is clean, but words which sit next to each other
+    // could still spell something offensive across a row or column.
+    let grid = bm.get_grid_str();
+    let used = bm.get_placed_words_for_serialization();
+    let offensive_words = dictionary.find_profanity_in_grid(&grid, &used);
+    if !offensive_words.is_empty() {
+        if all_config.generator.should_log_board_info {
+            println!("Rejected board, it contains: {offensive_words:?}");
+        }
+        return None;
+    }
+
+    let mut placed_themed_words: Vec<String> = bm
+        .get_used_words()
+        .into_iter()
+        .filter(|word| themed_words.contains(word))
+        .collect();
+    placed_themed_words.sort();
+
+    let mut generated_board = GeneratedBoard {
+        grid,
+        used,
+        all: all_words.join("_"),
+        letters: wheel_letters,
+        language: dictionary.get_language().code().to_owned(),
+        theme: theme.map(|theme| theme.to_owned()),
+        themed_words: placed_themed_words,
+        ambiguous_slots: Vec::new(),
+        too_ambiguous: false,
+        density,
+    };
+
+    // ==========================
+    //  AMBIGUITY CHECK
+    // ==========================
+
+    // The player could type a word which fits a slot, and still
+    // be told it's wrong because a different word was placed there.
+    if all_config.generator.ambiguity_action == AmbiguityAction::Ignore {
+        return Some(generated_board);
+    }
+    // The generator only builds valid boards, but if it ever doesn't,
+    // it's a failed attempt like any other.
+    generated_board.ambiguous_slots =
+        find_ambiguous_slots(&generated_board).ok()?;
+    generated_board.too_ambiguous = generated_board.ambiguous_slots.len()
+        > all_config.generator.max_ambiguous_slots;
+    if generated_board.too_ambiguous {
+        if all_config.generator.should_log_board_info {
+            println!(
+                "Board has {} ambiguous slots: {:?}",
+                generated_board.ambiguous_slots.len(),
+                generated_board.ambiguous_slots
+            );
+        }
+        if all_config.generator.ambiguity_action == AmbiguityAction::Reject {
+            return None;
+        }
+    }
+
+    Some(generated_board)
+}
+
+///
+/// Replaces some (random) letters of the wheel with wildcard tiles.
+///
+/// Example:
+/// ```
+/// // "mis?ses"
+/// add_wildcard_tiles("misuses", 1);
+/// ```
+///
+fn add_wildcard_tiles(letters: &str, number_of_wildcards: usize) -> String {
+    let mut wheel: Vec<char> = letters.chars().collect();
+    let indexes_to_replace: Vec<usize> =
+        shuffle(&(0..wheel.len()).collect::<Vec<usize>>());
+    for index in indexes_to_replace.into_iter().take(number_of_wildcards) {
+        wheel[index] = WILDCARD_LETTER;
+    }
+    wheel.into_iter().collect()
+}
+
+///
+/// Counts how many of the words placed on the board
+/// belong to the theme.
+///
+fn count_themed_words(
+    bm: &BoardManager,
+    themed_words: &HashSet<String>,
+) -> usize {
+    bm.get_used_words()
+        .iter()
+        .filter(|word| themed_words.contains(*word))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_dictionary;
+
+    #[test]
+    fn test_try_to_generate_a_themed_board() {
+        let mut all_config = Config::default();
+        all_config.generator.should_log_board_info = false;
+        all_config.generator.theme = Some("animals".to_owned());
+        all_config.generator.min_themed_words = 2;
+        let mut dictionary = DictionaryManager::new();
+        dictionary.load_precomputed_dictionary();
+        dictionary.load_theme_files(&all_config.app.themes_directory);
+
+        set_random_seed(Some(1));
+        let board = (0..all_config.generator.max_attempts_before_giving_up)
+            .find_map(|_| try_to_generate_a_board(&dictionary, &all_config))
+            .unwrap();
+        set_random_seed(None);
+
+        // Only the placed words of the theme are listed, and there
+        // are at least `min_themed_words` of them
+        let used_words: Vec<String> = board.used.keys().cloned().collect();
+        let mut themed_words =
+            dictionary.get_themed_words("animals", &used_words);
+        themed_words.sort();
+        assert_eq!(board.theme.as_deref(), Some("animals"));
+        assert_eq!(board.themed_words, themed_words);
+        assert!(board.themed_words.len() >= 2);
+    }
+
+    #[test]
+    fn test_all_words_include_the_anchors() {
+        let mut all_config = Config::default();
+        all_config.generator.should_log_board_info = false;
+        all_config.generator.max_words_to_place = 4;
+        let words = "emus mess misuse misuses muse sums uses";
+        let dictionary = make_dictionary(words);
+        let generate = |all_config: &Config, anchor_words: &[&str]| {
+            let subwords: Vec<String> = words
+                .split(' ')
+                .filter(|word| !anchor_words.contains(word))
+                .map(String::from)
+                .collect();
+            (0..all_config.generator.max_attempts_before_giving_up)
+                .find_map(|_| {
+                    try_to_generate_a_board_with_anchors(
+                        &dictionary,
+                        all_config,
+                        anchor_words,
+                        "misuses",
+                        &subwords,
+                    )
+                })
+                .unwrap()
+        };
+        set_random_seed(Some(1));
+
+        // From the subwords of one anchor
+        let board = generate(&all_config, &["misuses"]);
+        assert_eq!(board.all, words.replace(' ', "_"));
+
+        // Looked up from the whole wheel, for several anchors
+        let board = generate(&all_config, &["misuses", "misuse"]);
+        assert_eq!(board.all, words.replace(' ', "_"));
+
+        // Looked up with the wildcards of the wheel
+        all_config.generator.wildcard_tiles = 1;
+        let board = generate(&all_config, &["misuses"]);
+        let mut all_words: Vec<String> = dictionary
+            .find_all_words_for_letters_with_wildcards(&board.letters)
+            .into_iter()
+            .map(|wildcard_match| wildcard_match.word)
+            .collect();
+        all_words.sort();
+        assert!(all_words.contains(&"misuses".to_owned()));
+        assert_eq!(board.all, all_words.join("_"));
+        set_random_seed(None);
     }
 }
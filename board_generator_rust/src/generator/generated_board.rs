@@ -1,38 +1,85 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize, Serializer};
 
+use crate::analysis::AmbiguousSlot;
 use crate::board::Direction;
 
+use super::DensityReport;
+
 ///
 /// Because the purpose of this struct is for serialization
 /// I've abbreviated the field names for a smaller payload
 /// size
 ///
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WordLocationOnBoard {
     /// row index
-    #[serde(rename(serialize = "r"))]
+    #[serde(rename = "r")]
     pub row: usize,
     /// column index
-    #[serde(rename(serialize = "c"))]
+    #[serde(rename = "c")]
     pub col: usize,
     /// direction
-    #[serde(rename(serialize = "d"))]
+    #[serde(rename = "d")]
     pub direction: Direction,
 }
 
 pub type WordLocationsOnBoard = HashMap<String, WordLocationOnBoard>;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct GeneratedBoard {
     // The completed board/grid
     pub grid: String,
-    // All of the words that were used on the grid
+    // All of the words that were used on the grid. They're written
+    // in alphabetical order, so the same board always gives the
+    // same JSON.
+    #[serde(serialize_with = "serialize_sorted")]
     pub used: WordLocationsOnBoard,
     // All of the words capable of being built from
-    // the letters
+    // the letters, including the starting word(s)
     pub all: String,
     // The letters used to generate the board
     pub letters: String,
+    // The language code of the dictionary the words came from (Ex: "en")
+    #[serde(default)]
+    pub language: String,
+    // The theme of the board (Ex: "animals"), if it's a themed board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    // The words placed on the board which belong to the theme
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub themed_words: Vec<String>,
+    // Slots which more than one word could fill
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambiguous_slots: Vec<AmbiguousSlot>,
+    // The board has more ambiguous slots than the config allows
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub too_ambiguous: bool,
+    // How many words the densification pass added, if it ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<DensityReport>,
+}
+
+fn serialize_sorted<S: Serializer>(
+    used: &WordLocationsOnBoard,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    used.iter()
+        .collect::<BTreeMap<&String, &WordLocationOnBoard>>()
+        .serialize(serializer)
+}
+
+///
+/// Reads a file of previously generated boards (Ex: `boards.json`)
+///
+pub fn read_generated_boards(filepath: &str) -> Vec<GeneratedBoard> {
+    let boards_json = fs::read_to_string(filepath)
+        .unwrap_or_else(|_| panic!("Could not read '{filepath}'!"));
+    serde_json::from_str(&boards_json).unwrap_or_else(|_| {
+        panic!("Could not parse the boards in '{filepath}'!")
+    })
 }
@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::analysis::{find_ambiguous_slots, AmbiguityAction};
+use crate::board::{get_opposite_direction, BoardError, BoardManager};
+use crate::config::Config;
+use crate::dictionary::DictionaryManager;
+use crate::utils::{get_random_int_in_range, shuffle};
+
+use super::{densify_board, GeneratedBoard, WordLocationOnBoard};
+
+///
+/// Derives variants (siblings) of a board. Each variant keeps most
+/// of the board's layout, but swaps some of its words for other
+/// words of the same wheel, so it feels similar without being
+/// identical.
+///
+/// For each variant, `leaf_words_to_remove` leaf words (words which
+/// cross only one other word) are removed, and then different words
+/// from `all` are placed in their place, until the board has as many
+/// words as before. The variant may also be transposed, which mirrors
+/// it along its main diagonal. That's the only mirror which keeps
+/// every word reading left to right, and top to bottom.
+///
+/// Every variant passes the same checks as a generated board
+/// (validation, profanity and ambiguity), and no two variants,
+/// nor any variant and the original, have the same words.
+///
+/// Example:
+/// ```
+/// let boards = read_generated_boards("./boards.json");
+/// // Up to `variants_per_board` boards
+/// let variants = remix_board(&boards[0], &dictionary, &all_config)?;
+/// ```
+///
+pub fn remix_board(
+    board: &GeneratedBoard,
+    dictionary: &DictionaryManager,
+    all_config: &Config,
+) -> Result<Vec<GeneratedBoard>, BoardError> {
+    let config = &all_config.remix;
+    let original_words = get_sorted_words(board);
+    let mut variants: Vec<GeneratedBoard> = Vec::new();
+    let mut seen_words: HashSet<Vec<String>> = HashSet::new();
+    seen_words.insert(original_words.clone());
+
+    // Different words, so that a variant can't just put
+    // the removed words back where they were
+    let candidate_words: Vec<String> = board
+        .all
+        .split('_')
+        .filter(|word| {
+            !word.is_empty() && !original_words.contains(&word.to_string())
+        })
+        .map(|word| word.to_owned())
+        .collect();
+
+    for _ in 0..config.max_attempts {
+        if variants.len() >= config.variants_per_board {
+            break;
+        }
+
+        let mut bm = BoardManager::from_generated_board(board)?;
+        for _ in 0..config.leaf_words_to_remove {
+            let leaf_words = find_leaf_words(&bm);
+            if leaf_words.is_empty() {
+                break;
+            }
+            let leaf_word =
+                &leaf_words[get_random_int_in_range(0, leaf_words.len())];
+            bm.remove_word_from_board(leaf_word);
+        }
+        densify_board(
+            &mut bm,
+            &shuffle(&candidate_words),
+            original_words.len(),
+        );
+        if bm.get_number_of_used_words() < original_words.len() {
+            continue;
+        }
+
+        let mut variant = GeneratedBoard {
+            grid: bm.get_grid_str(),
+            used: bm.get_placed_words_for_serialization(),
+            all: board.all.to_owned(),
+            letters: board.letters.to_owned(),
+            language: board.language.to_owned(),
+            theme: board.theme.to_owned(),
+            themed_words: Vec::new(),
+            ambiguous_slots: Vec::new(),
+            too_ambiguous: false,
+            density: None,
+        };
+        if config.allow_transposing && get_random_int_in_range(0, 2) == 1 {
+            variant = transpose_board(&variant);
+        }
+
+        let variant_words = get_sorted_words(&variant);
+        if seen_words.contains(&variant_words)
+            || BoardManager::from_generated_board(&variant).is_err()
+            || !dictionary
+                .find_profanity_in_grid(&variant.grid, &variant.used)
+                .is_empty()
+        {
+            continue;
+        }
+
+        if let Some(theme) = variant.theme.as_deref() {
+            variant.themed_words = dictionary
+                .get_themed_words(theme, &variant_words)
+                .into_iter()
+                .sorted()
+                .collect();
+        }
+        let ambiguity_action = all_config.generator.ambiguity_action;
+        if ambiguity_action != AmbiguityAction::Ignore {
+            let Ok(ambiguous_slots) = find_ambiguous_slots(&variant) else {
+                continue;
+            };
+            variant.ambiguous_slots = ambiguous_slots;
+            variant.too_ambiguous = variant.ambiguous_slots.len()
+                > all_config.generator.max_ambiguous_slots;
+            if variant.too_ambiguous
+                && ambiguity_action == AmbiguityAction::Reject
+            {
+                continue;
+            }
+        }
+
+        seen_words.insert(variant_words);
+        variants.push(variant);
+    }
+
+    Ok(variants)
+}
+
+///
+/// Mirrors a board along its main diagonal. Rows become
+/// columns, and horizontal words become vertical words.
+///
+/// Example:
+/// ```
+/// // "ab#_c##_d##" -> "acd_b##_###"
+/// transpose_board(&board);
+/// ```
+///
+pub fn transpose_board(board: &GeneratedBoard) -> GeneratedBoard {
+    let rows: Vec<Vec<char>> = board
+        .grid
+        .split('_')
+        .map(|row| row.chars().collect())
+        .collect();
+    let grid = (0..rows.len())
+        .map(|col| {
+            rows.iter()
+                .filter_map(|row| row.get(col))
+                .collect::<String>()
+        })
+        .join("_");
+    let used = board
+        .used
+        .iter()
+        .map(|(word, location)| {
+            let transposed_location = WordLocationOnBoard {
+                row: location.col,
+                col: location.row,
+                direction: get_opposite_direction(&location.direction),
+            };
+            (word.to_owned(), transposed_location)
+        })
+        .collect();
+
+    GeneratedBoard {
+        grid,
+        used,
+        ..board.clone()
+    }
+}
+
+///
+/// Finds the words which cross exactly one other word. Removing
+/// one of them can't split the board in two.
+///
+fn find_leaf_words(bm: &BoardManager) -> Vec<String> {
+    bm.get_used_word_objects()
+        .into_iter()
+        .filter(|placed_word| {
+            let crossings = placed_word
+                .cells
+                .iter()
+                .filter_map(|cell| bm.get_cell_at(cell.row, cell.col))
+                .filter(|cell| {
+                    cell.is_used_horizontally() && cell.is_used_vertically()
+                })
+                .count();
+            crossings == 1
+        })
+        .map(|placed_word| placed_word.word)
+        // Sorted, so that a seeded remix is repeatable
+        .sorted()
+        .collect()
+}
+
+fn get_sorted_words(board: &GeneratedBoard) -> Vec<String> {
+    board.used.keys().cloned().sorted().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::test_utils;
+
+    fn make_board(all: &str) -> GeneratedBoard {
+        GeneratedBoard {
+            all: all.to_owned(),
+            ..test_utils::make_board(
+                5,
+                &[("stare", 1, 0, Direction::H), ("tea", 1, 1, Direction::V)],
+            )
+        }
+    }
+
+    #[test]
+    fn test_remix_board() {
+        let board = make_board("art_rat_sat_stare_tea");
+        let mut all_config = Config::default();
+        all_config.remix.leaf_words_to_remove = 1;
+        all_config.generator.ambiguity_action = AmbiguityAction::Flag;
+
+        let variants =
+            remix_board(&board, &DictionaryManager::new(), &all_config)
+                .unwrap();
+
+        assert!(!variants.is_empty());
+        let mut every_words = vec![get_sorted_words(&board)];
+        for variant in variants.iter() {
+            assert_eq!(variant.used.len(), 2);
+            assert!(BoardManager::from_generated_board(variant).is_ok());
+            every_words.push(get_sorted_words(variant));
+        }
+        assert_eq!(every_words.iter().unique().count(), every_words.len());
+    }
+
+    #[test]
+    fn test_transpose_board() {
+        let board = make_board("stare_tea");
+
+        let transposed = transpose_board(&board);
+
+        assert_eq!(transposed.grid, "#s###_#tea#_#a###_#r###_#e###");
+        assert_eq!(transposed.used["stare"].direction, Direction::V);
+        assert_eq!(transposed.used["tea"].direction, Direction::H);
+        assert!(BoardManager::from_generated_board(&transposed).is_ok());
+        assert_eq!(transpose_board(&transposed), board);
+    }
+}
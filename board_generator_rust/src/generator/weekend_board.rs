@@ -0,0 +1,125 @@
+use itertools::Itertools;
+
+use crate::config::Config;
+use crate::dictionary::{combine_sorted_letters, DictionaryManager};
+
+use super::{try_to_generate_a_board_with_anchors, GeneratedBoard};
+
+///
+/// Generates a (bigger) weekend board. Instead of a single starting
+/// word, the board starts from two crossing starting words (anchors).
+/// The wheel is every letter needed to build both of them, which can
+/// be up to `max_wheel_letters` letters, and the rest of the board is
+/// filled from the words that either anchor's letters can build. The
+/// board's `all` has every word of the whole wheel, so the dictionary
+/// needs its clean words loaded, as well as the precomputed words.
+///
+/// The board's size and number of words come from the weekend config,
+/// instead of the generator config. Weekend boards are never themed.
+///
+/// Example:
+/// ```
+/// // Ex: letters "addeikmnr", from "denmark" and "admired"
+/// let board = try_to_generate_a_weekend_board(&dictionary, &all_config);
+/// ```
+///
+pub fn try_to_generate_a_weekend_board(
+    dictionary: &DictionaryManager,
+    all_config: &Config,
+) -> Option<GeneratedBoard> {
+    let weekend_config = &all_config.weekend;
+    let mut all_config = all_config.clone();
+    all_config.generator.theme = None;
+    all_config.generator.board_dimensions = weekend_config.board_dimensions;
+    all_config.generator.max_words_to_place = weekend_config.max_words_to_place;
+    all_config.generator.max_words_to_consider =
+        weekend_config.max_words_to_consider;
+    all_config.generator.max_words_after_densifying =
+        weekend_config.max_words_after_densifying;
+
+    let (anchor, partner) =
+        dictionary.get_random_anchor_pair(weekend_config.max_wheel_letters)?;
+    let (_, anchor_subwords) = dictionary.get_starting_word(anchor)?;
+    let (_, partner_subwords) = dictionary.get_starting_word(partner)?;
+
+    // An anagram of the other anchor is one of its subwords
+    let subwords: Vec<String> = anchor_subwords
+        .iter()
+        .chain(partner_subwords.iter())
+        .filter(|word| *word != anchor && *word != partner)
+        .cloned()
+        .sorted()
+        .dedup()
+        .collect();
+    let letters: String = combine_sorted_letters(
+        &anchor.chars().sorted().collect::<Vec<char>>(),
+        &partner.chars().sorted().collect::<Vec<char>>(),
+    )
+    .into_iter()
+    .collect();
+
+    try_to_generate_a_board_with_anchors(
+        dictionary,
+        &all_config,
+        &[anchor, partner],
+        &letters,
+        &subwords,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardManager;
+    use crate::dictionary::sorted_letters_missing_from;
+    use crate::utils::word_length;
+
+    #[test]
+    fn test_try_to_generate_a_weekend_board() {
+        let mut all_config = Config::default();
+        let mut dictionary = DictionaryManager::new();
+        dictionary.load_precomputed_dictionary();
+        dictionary
+            .load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+        all_config.generator.should_log_board_info = false;
+
+        let board = (0..all_config.weekend.max_failed_attempts)
+            .find_map(|_| {
+                try_to_generate_a_weekend_board(&dictionary, &all_config)
+            })
+            .unwrap();
+
+        let letters: Vec<char> = board.letters.chars().collect();
+        assert!(
+            (7..=all_config.weekend.max_wheel_letters).contains(&letters.len())
+        );
+        assert!(letters.iter().is_sorted());
+        for word in board.used.keys() {
+            let word_letters: Vec<char> = word.chars().sorted().collect();
+            assert!(
+                sorted_letters_missing_from(&word_letters, &letters).is_empty()
+            );
+        }
+        // Both anchors are on the board
+        assert!(
+            board
+                .used
+                .keys()
+                .filter(|word| word_length(word) == 7)
+                .count()
+                >= 2
+        );
+        // Every word of the wheel can be found, not only the words
+        // of each anchor's letters
+        let all_words: Vec<&str> = board.all.split('_').collect();
+        assert_eq!(
+            all_words,
+            dictionary.find_all_words_for_letters(&board.letters, false)
+        );
+        assert!(board
+            .used
+            .keys()
+            .all(|word| all_words.contains(&word.as_str())));
+        assert!(BoardManager::from_generated_board(&board).is_ok());
+    }
+}
@@ -0,0 +1,3 @@
+mod ascii_board;
+
+pub use ascii_board::*;
@@ -0,0 +1,457 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use itertools::Itertools;
+
+use crate::analysis::find_ambiguous_slots;
+use crate::board::{BoardError, Direction, EMPTY_CELL_VALUE};
+use crate::dictionary::{
+    sorted_letters_missing_from, DictionaryManager, WILDCARD_LETTER,
+};
+use crate::generator::{GeneratedBoard, WordLocationOnBoard};
+
+/// Drawn for a cell without a letter in a hand-drawn board
+pub const ASCII_EMPTY_CELL: char = '.';
+
+///
+/// A problem with a hand-drawn board. Lines and columns are
+/// counted from 1, the same way a text editor counts them.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsciiBoardError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AsciiBoardErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsciiBoardErrorKind {
+    /// Only letters and `.` can be drawn
+    InvalidCharacter(char),
+    /// A letter with no letters beside, above or below it
+    IsolatedLetter(char),
+    UnknownWord(String),
+    /// A word can only be placed once on a board
+    DuplicateWord(String),
+    /// The word can't be built from the letters of the wheel
+    WordNotInWheel(String),
+    /// The word doesn't cross (or touch) the rest of the board
+    Disconnected(String),
+    /// The board was parsed, but still isn't valid
+    InvalidBoard(BoardError),
+}
+
+impl fmt::Display for AsciiBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            AsciiBoardErrorKind::InvalidCharacter(character) => {
+                write!(
+                    f,
+                    "'{character}' isn't a letter or '{ASCII_EMPTY_CELL}'"
+                )
+            }
+            AsciiBoardErrorKind::IsolatedLetter(letter) => {
+                write!(f, "'{letter}' isn't part of a word")
+            }
+            AsciiBoardErrorKind::UnknownWord(word) => {
+                write!(f, "'{word}' isn't in the dictionary")
+            }
+            AsciiBoardErrorKind::DuplicateWord(word) => {
+                write!(f, "'{word}' is already on the board")
+            }
+            AsciiBoardErrorKind::WordNotInWheel(word) => {
+                write!(f, "'{word}' can't be built from the wheel")
+            }
+            AsciiBoardErrorKind::Disconnected(word) => {
+                write!(f, "'{word}' isn't connected to the rest of the board")
+            }
+            AsciiBoardErrorKind::InvalidBoard(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+///
+/// Imports hand-drawn boards. Each board is drawn as rows of letters
+/// and `.` (empty cells), and boards are separated by blank lines.
+/// Every run of 2 or more letters (across or down) is a placed word.
+///
+/// The wheel of every board is `wheel` when it's given (it can contain
+/// `?` wildcard tiles), otherwise it's the fewest letters that can
+/// build every placed word.
+///
+/// Example:
+/// ```
+/// let text = "misuses\nu......\ns......\ne......";
+/// // Ok([GeneratedBoard { letters: "eimsssu", ... }])
+/// import_ascii_boards(text, None, &dictionary);
+/// ```
+///
+pub fn import_ascii_boards(
+    text: &str,
+    wheel: Option<&str>,
+    dictionary: &DictionaryManager,
+) -> Result<Vec<GeneratedBoard>, Vec<AsciiBoardError>> {
+    let mut boards = Vec::new();
+    let mut errors = Vec::new();
+
+    // Each group of (numbered) lines that aren't blank is a board
+    let numbered_lines = text.lines().enumerate();
+    for (is_blank, lines) in
+        &numbered_lines.group_by(|(_, line)| line.trim().is_empty())
+    {
+        if is_blank {
+            continue;
+        }
+        let lines: Vec<(usize, &str)> = lines.collect();
+        match import_ascii_board(&lines, wheel, dictionary) {
+            Ok(board) => boards.push(board),
+            Err(board_errors) => errors.extend(board_errors),
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(boards),
+        false => Err(errors),
+    }
+}
+
+///
+/// Imports a single board, from its (0 indexed) line numbers and lines.
+///
+fn import_ascii_board(
+    lines: &[(usize, &str)],
+    wheel: Option<&str>,
+    dictionary: &DictionaryManager,
+) -> Result<GeneratedBoard, Vec<AsciiBoardError>> {
+    let first_line = lines[0].0;
+    let error_at = |row: usize, col: usize, kind| AsciiBoardError {
+        line: first_line + row + 1,
+        column: col + 1,
+        kind,
+    };
+    let language = dictionary.get_language();
+
+    // =====================================================
+    //  READ THE GRID
+    // =====================================================
+
+    let mut errors = Vec::new();
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    for (row, (_, line)) in lines.iter().enumerate() {
+        let mut cells = Vec::new();
+        for (col, character) in line.trim_end().chars().enumerate() {
+            let letter = character.to_lowercase().next().unwrap_or(character);
+            if character == ASCII_EMPTY_CELL {
+                cells.push(EMPTY_CELL_VALUE);
+            } else if language.is_letter(letter) {
+                cells.push(letter);
+            } else {
+                errors.push(error_at(
+                    row,
+                    col,
+                    AsciiBoardErrorKind::InvalidCharacter(character),
+                ));
+            }
+        }
+        rows.push(cells);
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Boards are square, so pad every row (and add rows) to fit
+    let dimensions = rows
+        .iter()
+        .map(|cells| cells.len())
+        .chain([rows.len()])
+        .max()
+        .unwrap_or(0);
+    rows.resize(dimensions, Vec::new());
+    for cells in rows.iter_mut() {
+        cells.resize(dimensions, EMPTY_CELL_VALUE);
+    }
+    let is_letter = |row: usize, col: usize| {
+        rows.get(row)
+            .and_then(|cells| cells.get(col))
+            .is_some_and(|letter| *letter != EMPTY_CELL_VALUE)
+    };
+
+    // =====================================================
+    //  FIND THE WORDS (RUNS OF 2 OR MORE LETTERS)
+    // =====================================================
+
+    let mut words: Vec<(String, WordLocationOnBoard)> = Vec::new();
+    for row in 0..dimensions {
+        for col in 0..dimensions {
+            if !is_letter(row, col) {
+                continue;
+            }
+            let starts_across = (col == 0 || !is_letter(row, col - 1))
+                && is_letter(row, col + 1);
+            let starts_down = (row == 0 || !is_letter(row - 1, col))
+                && is_letter(row + 1, col);
+            if starts_across {
+                let word = (col..dimensions)
+                    .take_while(|col| is_letter(row, *col))
+                    .map(|col| rows[row][col])
+                    .collect();
+                words.push((word, location(row, col, Direction::H)));
+            }
+            if starts_down {
+                let word = (row..dimensions)
+                    .take_while(|row| is_letter(*row, col))
+                    .map(|row| rows[row][col])
+                    .collect();
+                words.push((word, location(row, col, Direction::V)));
+            }
+            let is_isolated = !is_letter(row, col + 1)
+                && !is_letter(row + 1, col)
+                && (col == 0 || !is_letter(row, col - 1))
+                && (row == 0 || !is_letter(row - 1, col));
+            if is_isolated {
+                errors.push(error_at(
+                    row,
+                    col,
+                    AsciiBoardErrorKind::IsolatedLetter(rows[row][col]),
+                ));
+            }
+        }
+    }
+
+    // =====================================================
+    //  CHECK THE WORDS AND THE WHEEL
+    // =====================================================
+
+    let wheel = match wheel {
+        Some(wheel) => wheel.to_lowercase(),
+        None => derive_wheel(words.iter().map(|(word, _)| word.as_str())),
+    };
+    let wheel_letters: Vec<char> = wheel
+        .chars()
+        .filter(|letter| *letter != WILDCARD_LETTER)
+        .sorted()
+        .collect();
+    let number_of_wildcards = wheel.chars().count() - wheel_letters.len();
+
+    let mut seen_words = HashSet::new();
+    for (word, at) in words.iter() {
+        let kind = if !seen_words.insert(word) {
+            AsciiBoardErrorKind::DuplicateWord(word.to_owned())
+        } else if !dictionary.is_word(word) {
+            AsciiBoardErrorKind::UnknownWord(word.to_owned())
+        } else if sorted_letters_missing_from(
+            &word.chars().sorted().collect::<Vec<char>>(),
+            &wheel_letters,
+        )
+        .len()
+            > number_of_wildcards
+        {
+            AsciiBoardErrorKind::WordNotInWheel(word.to_owned())
+        } else {
+            continue;
+        };
+        errors.push(error_at(at.row, at.col, kind));
+    }
+    for (word, at) in find_disconnected_words(&words) {
+        errors.push(error_at(
+            at.row,
+            at.col,
+            AsciiBoardErrorKind::Disconnected(word.to_owned()),
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // =====================================================
+    //  BUILD (AND VALIDATE) THE BOARD
+    // =====================================================
+
+    let all_words: Vec<String> = match number_of_wildcards {
+        0 => dictionary.find_all_words_for_letters(&wheel, false),
+        _ => dictionary
+            .find_all_words_for_letters_with_wildcards(&wheel)
+            .into_iter()
+            .map(|wildcard_match| wildcard_match.word)
+            .collect(),
+    };
+    let mut board = GeneratedBoard {
+        grid: rows.iter().map(|cells| cells.iter().join("")).join("_"),
+        used: words.into_iter().collect(),
+        all: all_words.join("_"),
+        letters: wheel,
+        language: language.code().to_owned(),
+        theme: None,
+        themed_words: Vec::new(),
+        ambiguous_slots: Vec::new(),
+        too_ambiguous: false,
+        density: None,
+    };
+    board.ambiguous_slots = find_ambiguous_slots(&board).map_err(|error| {
+        vec![error_at(0, 0, AsciiBoardErrorKind::InvalidBoard(error))]
+    })?;
+
+    Ok(board)
+}
+
+fn location(
+    row: usize,
+    col: usize,
+    direction: Direction,
+) -> WordLocationOnBoard {
+    WordLocationOnBoard {
+        row,
+        col,
+        direction,
+    }
+}
+
+///
+/// The fewest letters which can build every one of the words.
+/// Each letter is used as many times as the word which needs
+/// it the most. The letters are sorted.
+///
+/// Example:
+/// ```
+/// // "eimssu"
+/// derive_wheel(["muse", "miss"].into_iter());
+/// ```
+///
+fn derive_wheel<'a>(words: impl Iterator<Item = &'a str>) -> String {
+    let mut letter_counts: BTreeMap<char, usize> = BTreeMap::new();
+    for word in words {
+        let mut counts_in_word: BTreeMap<char, usize> = BTreeMap::new();
+        for letter in word.chars() {
+            *counts_in_word.entry(letter).or_insert(0) += 1;
+        }
+        for (letter, count) in counts_in_word {
+            let most = letter_counts.entry(letter).or_insert(0);
+            *most = count.max(*most);
+        }
+    }
+    letter_counts
+        .into_iter()
+        .flat_map(|(letter, count)| std::iter::repeat_n(letter, count))
+        .collect()
+}
+
+///
+/// Finds the words which can't be reached from the first word, by
+/// moving from word to word through the cells they share.
+///
+fn find_disconnected_words(
+    words: &[(String, WordLocationOnBoard)],
+) -> Vec<&(String, WordLocationOnBoard)> {
+    let cells_of = |(word, at): &(String, WordLocationOnBoard)| {
+        (0..word.chars().count())
+            .map(|offset| match at.direction {
+                Direction::H => (at.row, at.col + offset),
+                Direction::V => (at.row + offset, at.col),
+            })
+            .collect::<Vec<(usize, usize)>>()
+    };
+    let mut words_per_cell: HashMap<(usize, usize), Vec<usize>> =
+        HashMap::new();
+    for (index, word) in words.iter().enumerate() {
+        for cell in cells_of(word) {
+            words_per_cell.entry(cell).or_default().push(index);
+        }
+    }
+
+    let mut is_connected = vec![false; words.len()];
+    let mut to_visit: Vec<usize> =
+        (!words.is_empty()).then_some(0).into_iter().collect();
+    while let Some(index) = to_visit.pop() {
+        if is_connected[index] {
+            continue;
+        }
+        is_connected[index] = true;
+        for cell in cells_of(&words[index]) {
+            to_visit.extend(words_per_cell[&cell].iter().copied());
+        }
+    }
+
+    words
+        .iter()
+        .zip(is_connected)
+        .filter(|(_, is_connected)| !is_connected)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    fn make_dictionary() -> DictionaryManager {
+        test_utils::make_dictionary("emus mess miss muse sums misuses")
+    }
+
+    #[test]
+    fn test_import_ascii_boards() {
+        let text = "\
+misuses
+u
+s
+e
+
+..miss
+..e...
+..s...
+..s...
+";
+        let boards = import_ascii_boards(text, None, &make_dictionary())
+            .expect("Both boards are valid.");
+
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].letters, "eimsssu");
+        assert_eq!(boards[0].all, "emus_mess_miss_misuses_muse_sums");
+        assert_eq!(
+            boards[0].grid,
+            "misuses_u######_s######_e######_#######_#######_#######"
+        );
+        assert_eq!(boards[1].used["mess"], location(0, 2, Direction::V));
+        assert_eq!(boards[1].letters, "eimss");
+    }
+
+    #[test]
+    fn test_import_ascii_boards_errors() {
+        let text = "\
+sums..
+m....m
+u3....
+g.emus";
+        let errors = import_ascii_boards(text, Some("mu?"), &make_dictionary())
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![AsciiBoardError {
+                line: 3,
+                column: 2,
+                kind: AsciiBoardErrorKind::InvalidCharacter('3'),
+            }]
+        );
+
+        let text = text.replace('3', ".");
+        let errors: Vec<String> =
+            import_ascii_boards(&text, Some("mu?"), &make_dictionary())
+                .unwrap_err()
+                .iter()
+                .map(|error| error.to_string())
+                .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 2, column 6: 'm' isn't part of a word",
+                "line 1, column 1: 'sums' can't be built from the wheel",
+                "line 1, column 1: 'smug' isn't in the dictionary",
+                "line 4, column 3: 'emus' can't be built from the wheel",
+                "line 4, column 3: 'emus' isn't connected to the rest of the board",
+            ]
+        );
+    }
+}
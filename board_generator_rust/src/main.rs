@@ -1,26 +1,54 @@
+pub mod analysis;
 pub mod board;
 pub mod cli;
 pub mod config;
 pub mod dictionary;
+pub mod export;
 pub mod generator;
+pub mod import;
+pub mod pack;
+pub mod play;
+pub mod puzzles;
+pub mod render;
+pub mod report;
+#[cfg(test)]
+pub mod test_utils;
 pub mod utils;
 
-use clap::Parser;
+use board::BoardManager;
+use chrono::NaiveDate;
+use clap::{Parser, ValueEnum};
 use cli::RunMode;
 use colored::Colorize;
 use config::Config;
-use dictionary::DictionaryManager;
-use generator::{try_to_generate_a_board, GeneratedBoard};
+use dictionary::{DictionaryManager, Language, ProfanityFilter};
+use generator::{
+    generate_daily_puzzles, read_generated_boards, try_to_generate_a_board,
+    try_to_generate_a_weekend_board, DensityReport, GeneratedBoard,
+};
+use itertools::Itertools;
+use render::{render_board_svg, SvgView};
+use report::{DictionaryAudit, DictionaryDiff};
 use std::{
     collections::HashMap,
     fs::{self},
+    ops::RangeInclusive,
+    path::Path,
     time::Instant,
 };
 
 fn main() {
-    let all_config = config::Config::default();
+    let mut all_config = config::Config::default();
+
+    let args = cli::Args::parse();
+    if let Some(number_of_boards) = args.number_of_boards {
+        all_config.app.number_of_boards = number_of_boards;
+    }
+    if args.quiet {
+        all_config.generator.should_log_board_info = false;
+    }
 
-    match cli::Args::parse().mode {
+    match args.mode {
         // --mode files
         Some(RunMode::Files) => {
             println!("Processing New Dictionary Files.");
@@ -31,19 +59,693 @@ fn main() {
             println!("Generating using precomputed words.");
             generate_boards(&all_config);
         }
+        // --mode weekend
+        Some(RunMode::Weekend) => {
+            println!("Generating weekend boards from two starting words.");
+            generate_weekend_boards(&all_config);
+        }
+        // --mode pattern --pattern m?s?s
+        Some(RunMode::Pattern) => match args.pattern {
+            Some(pattern) => {
+                find_words_matching_pattern(&all_config, &pattern, args.letters)
+            }
+            None => println!("Error: Pass --pattern <pattern>"),
+        },
+        // --mode audit
+        Some(RunMode::Audit) => {
+            println!("Auditing the dictionary files.");
+            audit_dictionary(&all_config);
+        }
+        // --mode diff --old <file> --new <file>
+        Some(RunMode::Diff) => match (args.old, args.new) {
+            (Some(old), Some(new)) => {
+                let boards = args
+                    .boards
+                    .unwrap_or(all_config.app.output_filepath.to_owned());
+                diff_dictionaries(&all_config, &old, &new, &boards);
+            }
+            _ => println!("Error: Pass --old <file> --new <file>"),
+        },
+        // --mode play [--boards <file>] [--index <n>]
+        Some(RunMode::Play) => {
+            play_board(&all_config, args.boards, args.index);
+        }
+        // --mode render --index <n> | --range <start-end>
+        Some(RunMode::Render) => {
+            let range = match (args.range, args.index) {
+                (Some(range), _) => range,
+                (None, Some(index)) => index..=index,
+                (None, None) => {
+                    println!("Error: Pass --index <n> or --range <start-end>");
+                    return;
+                }
+            };
+            let boards = args
+                .boards
+                .unwrap_or(all_config.app.output_filepath.to_owned());
+            render_boards(&all_config, &boards, range, args.view);
+        }
+        // --mode book [--boards <file>] [--range <start-end>]
+        Some(RunMode::Book) => {
+            let boards = args
+                .boards
+                .unwrap_or(all_config.app.output_filepath.to_owned());
+            export_puzzle_book(&all_config, &boards, args.range);
+        }
+        // --mode pack [--boards <file>]
+        Some(RunMode::Pack) => {
+            let boards = args
+                .boards
+                .unwrap_or(all_config.app.output_filepath.to_owned());
+            build_level_pack(&all_config, &boards);
+        }
+        // --mode daily [--date <YYYY-MM-DD>] [--days <n>]
+        Some(RunMode::Daily) => match all_config.daily.salt {
+            Some(_) => {
+                let date = args
+                    .date
+                    .unwrap_or_else(|| chrono::Local::now().date_naive());
+                let days = args.days.unwrap_or(1);
+                generate_daily_calendar(&all_config, date, days);
+            }
+            None => println!("Error: Set the DAILY_PUZZLE_SALT variable"),
+        },
+        // --mode import --file <file> [--letters <wheel>]
+        Some(RunMode::Import) => match args.file {
+            Some(filepath) => {
+                import_ascii_boards(&all_config, &filepath, args.letters)
+            }
+            None => println!("Error: Pass --file <file>"),
+        },
+        // --mode remix --index <n> [--boards <file>]
+        Some(RunMode::Remix) => match args.index {
+            Some(index) => {
+                let boards = args
+                    .boards
+                    .unwrap_or(all_config.app.output_filepath.to_owned());
+                remix_board(&all_config, &boards, index);
+            }
+            None => println!("Error: Pass --index <n>"),
+        },
+        // --mode kriss-kross [--boards <file>] [--range <start-end>]
+        Some(RunMode::KrissKross) => {
+            let boards = args
+                .boards
+                .unwrap_or(all_config.app.output_filepath.to_owned());
+            export_kriss_krosses(&all_config, &boards, args.range);
+        }
+        // --mode codeword [--boards <file>] [--range <start-end>]
+        Some(RunMode::Codeword) => {
+            let boards = args
+                .boards
+                .unwrap_or(all_config.app.output_filepath.to_owned());
+            generate_codewords(&all_config, &boards, args.range);
+        }
+        // --mode word-search [--letters <letters>]
+        Some(RunMode::WordSearch) => {
+            generate_word_searches(&all_config, args.letters);
+        }
         None => {
-            println!("Error: Pass --mode <files | generate>");
+            println!(
+                "Error: Pass --mode <files | generate | weekend | pattern | audit | diff | play | render | book | pack | daily | import | remix | word-search | kriss-kross | codeword>"
+            );
         }
     }
 }
 
+fn find_words_matching_pattern(
+    all_config: &Config,
+    pattern: &str,
+    letters: Option<String>,
+) {
+    let mut dictionary = DictionaryManager::new();
+    dictionary.set_language(all_config.app.language);
+    dictionary.load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+
+    let pattern = pattern.to_lowercase();
+    let letters = letters.map(|letters| letters.to_lowercase());
+    let matching_words =
+        dictionary.find_words_matching_pattern(&pattern, letters.as_deref());
+
+    for word in matching_words.iter() {
+        println!("{word}");
+    }
+    println!("{} words match '{pattern}'.", matching_words.len());
+}
+
+fn audit_dictionary(all_config: &Config) {
+    let clean_words = dictionary::read_clean_dictionary(
+        &all_config.app.clean_dictionary_filepath,
+    );
+    let precomputed_words = dictionary::read_precomputed_file(
+        &all_config.app.precomputed_words_filepath,
+    )
+    .expect("Could not read the precomputed word-map file.");
+
+    let audit = DictionaryAudit::new(
+        &clean_words,
+        &precomputed_words,
+        all_config.generator.min_words_to_consider_letters,
+        all_config.app.language,
+    );
+    audit.print_table();
+
+    if let Ok(json_to_write) = serde_json::to_string_pretty(&audit) {
+        fs::write(&all_config.app.audit_report_filepath, json_to_write)
+            .expect("Could not write the audit report to a file.");
+    }
+}
+
+fn diff_dictionaries(
+    all_config: &Config,
+    old_filepath: &str,
+    new_filepath: &str,
+    boards_filepath: &str,
+) {
+    let old_words = DictionaryDiff::read_dictionary_words(old_filepath);
+    let new_words = DictionaryDiff::read_dictionary_words(new_filepath);
+    let boards = match Path::new(boards_filepath).exists() {
+        true => read_generated_boards(boards_filepath),
+        false => {
+            println!("No boards found at '{boards_filepath}'.");
+            Vec::new()
+        }
+    };
+
+    let diff = DictionaryDiff::new(&old_words, &new_words, &boards);
+    diff.print_table();
+
+    if let Ok(json_to_write) = serde_json::to_string_pretty(&diff) {
+        fs::write(&all_config.app.diff_report_filepath, json_to_write)
+            .expect("Could not write the diff report to a file.");
+    }
+}
+
+fn play_board(
+    all_config: &Config,
+    boards_filepath: Option<String>,
+    board_index: Option<usize>,
+) {
+    let board = match boards_filepath {
+        Some(boards_filepath) => {
+            let mut boards = read_generated_boards(&boards_filepath);
+            if boards.is_empty() {
+                println!("There are no boards in '{boards_filepath}'.");
+                return;
+            }
+            let index = board_index.unwrap_or_else(|| {
+                utils::get_random_int_in_range(0, boards.len())
+            });
+            if index >= boards.len() {
+                println!("There are only {} boards.", boards.len());
+                return;
+            }
+            println!("Playing board #{index} of '{boards_filepath}'.");
+            boards.swap_remove(index)
+        }
+        None => {
+            let mut all_config = all_config.clone();
+            all_config.generator.should_log_board_info = false;
+            let dictionary = load_dictionary_for_generating(&all_config);
+            let board = (0..all_config.generator.max_attempts_before_giving_up)
+                .find_map(|_| {
+                    try_to_generate_a_board(&dictionary, &all_config)
+                });
+            match board {
+                Some(board) => board,
+                None => {
+                    println!("Error: Could not generate a board to play");
+                    return;
+                }
+            }
+        }
+    };
+
+    play::play_in_terminal(&board);
+}
+
+fn render_boards(
+    all_config: &Config,
+    boards_filepath: &str,
+    range: RangeInclusive<usize>,
+    view: Option<SvgView>,
+) {
+    let boards = read_generated_boards(boards_filepath);
+    let views = match view {
+        Some(view) => vec![view],
+        None => SvgView::value_variants().to_vec(),
+    };
+
+    let output_directory = &all_config.render.output_directory;
+    fs::create_dir_all(output_directory)
+        .expect("Could not create the render output directory.");
+
+    for index in range {
+        let Some(board) = boards.get(index) else {
+            println!("There is no board #{index}, there are {}.", boards.len());
+            break;
+        };
+        if let Err(error) = BoardManager::from_generated_board(board) {
+            println!("Rejected board #{index}: {error}");
+            continue;
+        }
+        for view in views.iter() {
+            let svg = render_board_svg(board, *view, &all_config.render);
+            let filepath =
+                format!("{output_directory}/board_{index}_{}.svg", view.name());
+            fs::write(&filepath, svg).expect("Could not write the SVG file.");
+            println!("Rendered '{filepath}'");
+        }
+    }
+}
+
+fn export_puzzle_book(
+    all_config: &Config,
+    boards_filepath: &str,
+    range: Option<RangeInclusive<usize>>,
+) {
+    let boards = keep_valid_boards(
+        read_generated_boards(boards_filepath)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                range.as_ref().is_none_or(|range| range.contains(index))
+            }),
+    );
+
+    let html = export::create_puzzle_book(
+        &boards,
+        &all_config.export,
+        &all_config.render,
+    );
+
+    let filepath = &all_config.export.puzzle_book_filepath;
+    if let Some(directory) = Path::new(filepath).parent() {
+        fs::create_dir_all(directory)
+            .expect("Could not create the puzzle book's directory.");
+    }
+    fs::write(filepath, html).expect("Could not write the puzzle book.");
+    println!("Wrote {} puzzles to '{filepath}'", boards.len());
+}
+
+fn export_kriss_krosses(
+    all_config: &Config,
+    boards_filepath: &str,
+    range: Option<RangeInclusive<usize>>,
+) {
+    let boards = read_generated_boards(boards_filepath);
+    let range = range.unwrap_or(0..=boards.len().saturating_sub(1));
+
+    let mut puzzles: Vec<export::KrissKrossPuzzle> = Vec::new();
+    for (index, board) in boards.iter().enumerate() {
+        if !range.contains(&index) {
+            continue;
+        }
+        match export::export_kriss_kross(board) {
+            Ok(puzzle) => puzzles.push(puzzle),
+            Err(error) => println!("Rejected board #{index}: {error}"),
+        }
+    }
+
+    let filepath = &all_config.export.kriss_kross_filepath;
+    if let Ok(json_to_write) = serde_json::to_string(&puzzles) {
+        fs::write(filepath, json_to_write)
+            .expect("Could not write the kriss-kross puzzles to a file.");
+    }
+    println!("Wrote {} kriss-kross puzzles to '{filepath}'", puzzles.len());
+}
+
+fn build_level_pack(all_config: &Config, boards_filepath: &str) {
+    let boards = keep_valid_boards(
+        read_generated_boards(boards_filepath)
+            .into_iter()
+            .enumerate(),
+    );
+    let pack = pack::build_level_pack(&boards, &all_config.pack);
+
+    for level in pack.levels.iter() {
+        println!(
+            "Chapter {:>2}, Level {:>2}: {} (wheel {}, difficulty {:.1}){}",
+            level.chapter,
+            level.level,
+            level.board.letters,
+            level.wheel_size,
+            level.difficulty,
+            if level.is_boss { " BOSS" } else { "" },
+        );
+    }
+
+    if let Ok(json_to_write) = serde_json::to_string(&pack) {
+        fs::write(&all_config.pack.pack_filepath, json_to_write)
+            .expect("Could not write the pack to a file.");
+    }
+    println!(
+        "Wrote {} levels in {} chapters to '{}'",
+        pack.number_of_levels,
+        pack.number_of_chapters,
+        all_config.pack.pack_filepath
+    );
+}
+
+///
+/// Drops the boards whose grid doesn't match their placed words, since
+/// they can't be laid out. Each board comes with its index in the file.
+///
+fn keep_valid_boards(
+    boards: impl Iterator<Item = (usize, GeneratedBoard)>,
+) -> Vec<GeneratedBoard> {
+    boards
+        .filter(|(index, board)| {
+            match BoardManager::from_generated_board(board) {
+                Ok(_) => true,
+                Err(error) => {
+                    println!("Rejected board #{index}: {error}");
+                    false
+                }
+            }
+        })
+        .map(|(_, board)| board)
+        .collect()
+}
+
+fn remix_board(all_config: &Config, boards_filepath: &str, board_index: usize) {
+    let boards = read_generated_boards(boards_filepath);
+    let Some(board) = boards.get(board_index) else {
+        println!("There are only {} boards.", boards.len());
+        return;
+    };
+
+    let dictionary = load_dictionary_for_generating(all_config);
+    let variants = match generator::remix_board(board, &dictionary, all_config)
+    {
+        Ok(variants) => variants,
+        Err(error) => {
+            println!("Board #{board_index} is not valid: {error}");
+            return;
+        }
+    };
+
+    for (number, variant) in variants.iter().enumerate() {
+        let removed_words = board
+            .used
+            .keys()
+            .filter(|word| !variant.used.contains_key(*word))
+            .sorted()
+            .join(", ");
+        let added_words = variant
+            .used
+            .keys()
+            .filter(|word| !board.used.contains_key(*word))
+            .sorted()
+            .join(", ");
+        println!("Variant {number}: -[{removed_words}] +[{added_words}]");
+    }
+
+    if let Ok(json_to_write) = serde_json::to_string(&variants) {
+        fs::write(&all_config.remix.remix_filepath, json_to_write)
+            .expect("Could not write the variants to a file.");
+    }
+    println!(
+        "Wrote {} variants of board #{board_index} to '{}'",
+        variants.len(),
+        all_config.remix.remix_filepath
+    );
+}
+
+fn generate_codewords(
+    all_config: &Config,
+    boards_filepath: &str,
+    range: Option<RangeInclusive<usize>>,
+) {
+    let boards = read_generated_boards(boards_filepath);
+    let range = range.unwrap_or(0..=boards.len().saturating_sub(1));
+    // The player could think of any word, not only the ones on boards
+    let mut dictionary = DictionaryManager::new();
+    dictionary.set_language(all_config.app.language);
+    dictionary.load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+
+    let mut puzzles: Vec<puzzles::CodewordPuzzle> = Vec::new();
+    for (index, board) in boards.iter().enumerate() {
+        if !range.contains(&index) {
+            continue;
+        }
+        match puzzles::generate_codeword(
+            board,
+            &dictionary,
+            &all_config.codeword,
+        ) {
+            Ok(puzzle) => {
+                println!(
+                    "Board #{index}: {} letters, {} starters",
+                    puzzle.key.len(),
+                    puzzle.starters.len()
+                );
+                puzzles.push(puzzle);
+            }
+            Err(error) => println!("Rejected board #{index}: {error}"),
+        }
+    }
+
+    let filepath = &all_config.codeword.puzzles_filepath;
+    if let Ok(json_to_write) = serde_json::to_string(&puzzles) {
+        fs::write(filepath, json_to_write)
+            .expect("Could not write the codewords to a file.");
+    }
+    println!("Wrote {} codewords to '{filepath}'", puzzles.len());
+}
+
+fn generate_word_searches(all_config: &Config, letters: Option<String>) {
+    let config = &all_config.word_search;
+    let mut dictionary = DictionaryManager::new();
+    dictionary.set_language(all_config.app.language);
+    dictionary.load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+    dictionary.load_profanity_filter(ProfanityFilter::from_files(
+        &all_config.app.bad_words_filepath,
+        &all_config.app.profanity_allowlist_filepath,
+        all_config.app.min_profanity_substring_length,
+    ));
+
+    let mut puzzles: Vec<puzzles::WordSearchPuzzle> = Vec::new();
+    let mut failed_attempts: usize = 0;
+    while puzzles.len() < config.number_of_puzzles {
+        let puzzle_letters = match &letters {
+            Some(letters) => letters.to_lowercase(),
+            None => match dictionary.get_random_word_by_length(7) {
+                Some(word) => word,
+                None => {
+                    println!("The dictionary has no 7-letter words.");
+                    return;
+                }
+            },
+        };
+        match puzzles::generate_word_search(
+            &puzzle_letters,
+            &dictionary,
+            config,
+        ) {
+            Some(puzzle) => puzzles.push(puzzle),
+            None => failed_attempts += 1,
+        }
+        // The words are hidden at random, so the same letters can
+        // fail once and work the next time, but not forever. Random
+        // letters can keep failing too, if too few of the dictionary's
+        // words can be hidden.
+        if failed_attempts >= config.max_failed_attempts {
+            match letters {
+                Some(_) => println!(
+                    "Could not hide {} words of '{puzzle_letters}'.",
+                    config.min_words
+                ),
+                None => println!(
+                    "Gave up after {failed_attempts} failed attempts, with {} of {} word searches.",
+                    puzzles.len(),
+                    config.number_of_puzzles
+                ),
+            }
+            return;
+        }
+    }
+
+    for puzzle in puzzles.iter() {
+        println!("{}\n", puzzle.grid.replace('_', "\n"));
+        println!(
+            "Find: {}\n",
+            puzzle
+                .words
+                .iter()
+                .map(|hidden| &hidden.word)
+                .sorted()
+                .join(", ")
+        );
+    }
+
+    if let Ok(json_to_write) = serde_json::to_string(&puzzles) {
+        fs::write(&config.puzzles_filepath, json_to_write)
+            .expect("Could not write the word searches to a file.");
+    }
+    println!(
+        "Wrote {} word searches to '{}' ({failed_attempts} failed attempts)",
+        puzzles.len(),
+        config.puzzles_filepath
+    );
+}
+
+fn generate_daily_calendar(
+    all_config: &Config,
+    first_date: NaiveDate,
+    number_of_days: usize,
+) {
+    let dictionary = load_dictionary_for_generating(all_config);
+    let calendar = generate_daily_puzzles(
+        &dictionary,
+        all_config,
+        first_date,
+        number_of_days,
+    );
+
+    for (date, board) in calendar.iter() {
+        println!("{date}: '{}' ({} words)", board.letters, board.used.len());
+    }
+    if calendar.len() < number_of_days {
+        println!(
+            "{} of {number_of_days} days have no puzzle. The epoch is {}.",
+            number_of_days - calendar.len(),
+            all_config.daily.epoch
+        );
+    }
+
+    if let Ok(json_to_write) = serde_json::to_string(&calendar) {
+        fs::write(&all_config.daily.calendar_filepath, json_to_write)
+            .expect("Could not write the daily puzzles to a file.");
+    }
+}
+
+fn import_ascii_boards(
+    all_config: &Config,
+    filepath: &str,
+    wheel: Option<String>,
+) {
+    let text = fs::read_to_string(filepath)
+        .unwrap_or_else(|_| panic!("Could not read '{filepath}'."));
+    let mut dictionary = DictionaryManager::new();
+    dictionary.set_language(all_config.app.language);
+    dictionary.load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+
+    let boards =
+        match import::import_ascii_boards(&text, wheel.as_deref(), &dictionary)
+        {
+            Ok(boards) => boards,
+            Err(errors) => {
+                for error in errors.iter() {
+                    println!("{filepath}: {error}");
+                }
+                println!(
+                    "Could not import '{filepath}', {} errors.",
+                    errors.len()
+                );
+                return;
+            }
+        };
+
+    for board in boards.iter() {
+        if let Ok(bm) = BoardManager::from_generated_board(board) {
+            bm.debug();
+        }
+        println!(
+            "Letters: '{}', {} words placed, {} ambiguous slots.",
+            board.letters,
+            board.used.len(),
+            board.ambiguous_slots.len()
+        );
+    }
+
+    let output_filepath = &all_config.app.imported_boards_filepath;
+    if let Ok(json_to_write) = serde_json::to_string(&boards) {
+        fs::write(output_filepath, json_to_write)
+            .expect("Could not write the imported boards to a file.");
+    }
+    println!("Wrote {} boards to '{output_filepath}'", boards.len());
+}
+
+///
+/// Loads everything the generator needs into a new DictionaryManager
+///
+fn load_dictionary_for_generating(all_config: &Config) -> DictionaryManager {
+    let mut dictionary = DictionaryManager::new();
+
+    dictionary.set_language(all_config.app.language);
+    match all_config.app.language {
+        Language::English => dictionary.load_precomputed_dictionary(),
+        _ => dictionary.load_precomputed_dictionary_file(
+            &all_config.app.precomputed_words_filepath,
+        ),
+    }
+    // Wildcard lookups need every word of the dictionary,
+    // not only the precomputed starting words.
+    if all_config.generator.wildcard_tiles > 0 {
+        dictionary
+            .load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+    }
+    dictionary.load_theme_files(&all_config.app.themes_directory);
+    dictionary.load_profanity_filter(ProfanityFilter::from_files(
+        &all_config.app.bad_words_filepath,
+        &all_config.app.profanity_allowlist_filepath,
+        all_config.app.min_profanity_substring_length,
+    ));
+
+    dictionary
+}
+
+fn generate_weekend_boards(all_config: &Config) {
+    let config = &all_config.weekend;
+    let mut all_config = all_config.clone();
+    all_config.generator.should_log_board_info = false;
+    let mut dictionary = load_dictionary_for_generating(&all_config);
+    // Every word the wheel can make is looked up, not only the
+    // precomputed words of each starting word
+    if all_config.generator.wildcard_tiles == 0 {
+        dictionary
+            .load_clean_dictionary(&all_config.app.clean_dictionary_filepath);
+    }
+
+    let mut weekend_boards: Vec<GeneratedBoard> = Vec::new();
+    let mut failed_attempts: usize = 0;
+    while weekend_boards.len() < config.number_of_boards {
+        match try_to_generate_a_weekend_board(&dictionary, &all_config) {
+            Some(board) => {
+                println!(
+                    "Board #{}: '{}' ({} letters, {} words)",
+                    weekend_boards.len(),
+                    board.letters,
+                    board.letters.chars().count(),
+                    board.used.len()
+                );
+                weekend_boards.push(board);
+            }
+            None => failed_attempts += 1,
+        }
+        if failed_attempts >= config.max_failed_attempts {
+            println!("Gave up after {failed_attempts} failed attempts.");
+            break;
+        }
+    }
+
+    if let Ok(json_to_write) = serde_json::to_string(&weekend_boards) {
+        fs::write(&config.boards_filepath, json_to_write)
+            .expect("Could not write the weekend boards to a file.");
+    }
+    println!(
+        "Wrote {} weekend boards to '{}' ({failed_attempts} failed attempts)",
+        weekend_boards.len(),
+        config.boards_filepath
+    );
+}
+
 fn generate_boards(all_config: &Config) {
     //
     // Create a dictionary manager
     // Load the dictionary file, parse the words.
-    let mut dictionary = DictionaryManager::new();
-
-    dictionary.load_precomputed_dictionary();
+    let dictionary = load_dictionary_for_generating(all_config);
 
     let mut generated_boards: Vec<GeneratedBoard> = Vec::new();
     let mut times_map: HashMap<u128, usize> = HashMap::new();
@@ -57,16 +759,13 @@ fn generate_boards(all_config: &Config) {
     while generated_boards.len() < all_config.app.number_of_boards {
         let time_per_board = Instant::now();
 
-        match try_to_generate_a_board(
-            &dictionary,
-            all_config,
-        ) {
+        match try_to_generate_a_board(&dictionary, all_config) {
             Some(new_board) => {
                 *times_map
                     .entry(time_per_board.elapsed().as_millis())
                     .or_insert(0) += 1;
                 generated_boards.push(new_board);
-                if generated_boards.len() % 100 == 0 {
+                if generated_boards.len().is_multiple_of(100) {
                     println!(
                         "{} / {} boards",
                         generated_boards.len(),
@@ -99,6 +798,17 @@ fn generate_boards(all_config: &Config) {
         println!("Generation Times (ms: frequency)\n{times_map:#?}");
         println!("{blank_line}");
         println!("Failed Attempts: {failed_attempts}");
+        if all_config.generator.should_densify_boards {
+            let densities: Vec<DensityReport> = generated_boards
+                .iter()
+                .filter_map(|board| board.density)
+                .collect();
+            println!(
+                "Densification added {} words and {} crossings.",
+                densities.iter().map(|d| d.words_added).sum::<usize>(),
+                densities.iter().map(|d| d.crossings_added).sum::<usize>()
+            );
+        }
         println!("{blank_line}\n{blank_line}");
     }
 
@@ -0,0 +1,3 @@
+mod level_pack;
+
+pub use level_pack::*;
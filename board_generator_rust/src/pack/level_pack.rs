@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::analysis::get_difficulty;
+use crate::config::PackConfig;
+use crate::generator::GeneratedBoard;
+use crate::utils::word_length;
+
+///
+/// One level of a pack (a campaign of boards).
+///
+#[derive(Clone, Debug, Serialize)]
+pub struct PackLevel {
+    /// Chapters and levels are counted from 1
+    pub chapter: usize,
+    /// The level number within the chapter
+    pub level: usize,
+    /// The level number within the entire pack
+    pub overall_level: usize,
+    /// Boss levels are themed boards, every `boss_every` levels
+    pub is_boss: bool,
+    pub difficulty: f32,
+    pub wheel_size: usize,
+    /// The index of the board within the pool it was chosen from
+    pub board_index: usize,
+    pub board: GeneratedBoard,
+}
+
+///
+/// An ordered campaign of boards, ready to be shipped.
+///
+#[derive(Clone, Debug, Serialize)]
+pub struct PackManifest {
+    pub number_of_chapters: usize,
+    pub number_of_levels: usize,
+    pub levels: Vec<PackLevel>,
+}
+
+///
+/// Orders a pool of generated boards into a sequence of levels:
+///
+/// 1) Boards are sorted by their difficulty, plus `wheel_size_weight`
+///    for each letter of their wheel. So the difficulty ramps up, and
+///    the wheel grows over time. The hardest boards of a wheel size
+///    come after the easiest boards of the next size, so difficulty
+///    doesn't drop back down every time the wheel grows.
+/// 2) Every `boss_every` levels, a themed board is used as a "boss".
+///    Themed boards are saved for the boss levels, unless the
+///    unthemed boards run out.
+/// 3) A board isn't used if the same wheel (starting word) was used
+///    within the last `repeat_window` levels. The next easiest board
+///    is used instead, or if every board left repeats, the repeat is
+///    allowed.
+///
+pub fn build_level_pack(
+    boards: &[GeneratedBoard],
+    pack_config: &PackConfig,
+) -> PackManifest {
+    let difficulties: Vec<f32> = boards.iter().map(get_difficulty).collect();
+    let wheel_sizes: Vec<usize> = boards
+        .iter()
+        .map(|board| word_length(&board.letters))
+        .collect();
+    // Boards with the same wheel letters have the same starting
+    // word (or an anagram of it)
+    let wheels: Vec<String> = boards
+        .iter()
+        .map(|board| board.letters.chars().sorted().collect())
+        .collect();
+
+    let (mut boss_pool, mut regular_pool): (Vec<usize>, Vec<usize>) = (0
+        ..boards.len())
+        .sorted_by(|a, b| {
+            let ramp = |index: usize| {
+                difficulties[index]
+                    + pack_config.wheel_size_weight * wheel_sizes[index] as f32
+            };
+            ramp(*a).total_cmp(&ramp(*b))
+        })
+        .partition(|index| boards[*index].theme.is_some());
+
+    let levels_per_chapter = pack_config.levels_per_chapter.max(1);
+    let mut recent_wheels: VecDeque<&String> = VecDeque::new();
+    let mut levels = Vec::new();
+
+    while !boss_pool.is_empty() || !regular_pool.is_empty() {
+        let overall_level = levels.len() + 1;
+        let is_boss_level = pack_config.boss_every > 0
+            && overall_level % pack_config.boss_every == 0;
+
+        // When one pool runs out, the other pool is used instead
+        let (first_pool, second_pool) = match is_boss_level {
+            true => (&mut boss_pool, &mut regular_pool),
+            false => (&mut regular_pool, &mut boss_pool),
+        };
+        let (is_boss, board_index) =
+            match take_next_board(first_pool, &wheels, &recent_wheels) {
+                Some(index) => (is_boss_level, index),
+                None => (
+                    false,
+                    take_next_board(second_pool, &wheels, &recent_wheels)
+                        .unwrap_or_default(),
+                ),
+            };
+
+        recent_wheels.push_back(&wheels[board_index]);
+        if recent_wheels.len() > pack_config.repeat_window {
+            recent_wheels.pop_front();
+        }
+
+        levels.push(PackLevel {
+            chapter: (overall_level - 1) / levels_per_chapter + 1,
+            level: (overall_level - 1) % levels_per_chapter + 1,
+            overall_level,
+            is_boss,
+            difficulty: difficulties[board_index],
+            wheel_size: wheel_sizes[board_index],
+            board_index,
+            board: boards[board_index].clone(),
+        });
+    }
+
+    PackManifest {
+        number_of_chapters: levels.len().div_ceil(levels_per_chapter),
+        number_of_levels: levels.len(),
+        levels,
+    }
+}
+
+///
+/// Takes the first (easiest) board out of the pool whose wheel hasn't
+/// been used recently. If they've all been used recently, then the
+/// first board is taken anyway.
+///
+fn take_next_board(
+    pool: &mut Vec<usize>,
+    wheels: &[String],
+    recent_wheels: &VecDeque<&String>,
+) -> Option<usize> {
+    if pool.is_empty() {
+        return None;
+    }
+    let position = pool
+        .iter()
+        .position(|index| !recent_wheels.contains(&&wheels[*index]))
+        .unwrap_or(0);
+    Some(pool.remove(position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::AmbiguousSlot;
+    use crate::board::Direction;
+    use crate::test_utils;
+
+    ///
+    /// A board with a single word across the top
+    ///
+    fn make_board(letters: &str, theme: Option<&str>) -> GeneratedBoard {
+        GeneratedBoard {
+            theme: theme.map(|theme| theme.to_owned()),
+            ..test_utils::make_board(
+                word_length(letters),
+                &[(letters, 0, 0, Direction::H)],
+            )
+        }
+    }
+
+    #[test]
+    fn test_build_level_pack() {
+        let boards = vec![
+            make_board("misuses", None),
+            make_board("bears", Some("animals")),
+            make_board("misuses", None),
+            make_board("seven", None),
+            make_board("sixty", None),
+            make_board("five", None),
+            make_board("emus", None),
+        ];
+        let pack_config = PackConfig {
+            pack_filepath: String::new(),
+            levels_per_chapter: 4,
+            boss_every: 3,
+            repeat_window: 2,
+            wheel_size_weight: 5.0,
+        };
+
+        let pack = build_level_pack(&boards, &pack_config);
+        let letters: Vec<&str> = pack
+            .levels
+            .iter()
+            .map(|level| level.board.letters.as_str())
+            .collect();
+
+        assert_eq!(
+            letters,
+            vec![
+                "five", "emus", "bears", "seven", "sixty", "misuses", "misuses"
+            ]
+        );
+        assert!(pack.levels[2].is_boss);
+        assert!(!pack.levels[5].is_boss);
+        assert_eq!(pack.number_of_chapters, 2);
+        assert_eq!((pack.levels[4].chapter, pack.levels[4].level), (2, 1));
+    }
+
+    #[test]
+    fn test_build_level_pack_ramps_difficulty() {
+        // Ambiguous slots make "seven" the hardest 5 letter board
+        let mut seven = make_board("seven", None);
+        seven.ambiguous_slots = vec![
+            AmbiguousSlot {
+                word: "seven".to_owned(),
+                pattern: "?????".to_owned(),
+                candidates: Vec::new(),
+            };
+            12
+        ];
+        let boards = vec![
+            make_board("misuses", None),
+            seven,
+            make_board("breads", None),
+            make_board("bears", None),
+        ];
+        let pack_config = PackConfig {
+            pack_filepath: String::new(),
+            levels_per_chapter: 4,
+            boss_every: 0,
+            repeat_window: 0,
+            wheel_size_weight: 5.0,
+        };
+
+        let pack = build_level_pack(&boards, &pack_config);
+        let letters: Vec<&str> = pack
+            .levels
+            .iter()
+            .map(|level| level.board.letters.as_str())
+            .collect();
+        let difficulties: Vec<f32> =
+            pack.levels.iter().map(|level| level.difficulty).collect();
+
+        // Sorting by wheel size first would put "breads" (23) after
+        // "seven" (31)
+        assert_eq!(letters, vec!["bears", "breads", "seven", "misuses"]);
+        assert_eq!(difficulties, vec![19.0, 23.0, 31.0, 27.0]);
+        // Difficulty only drops when the wheel grows, and by less than
+        // the growth is worth
+        for pair in pack.levels.windows(2) {
+            let wheel_growth =
+                pair[1].wheel_size as f32 - pair[0].wheel_size as f32;
+            assert!(
+                pair[0].difficulty - pair[1].difficulty
+                    <= pack_config.wheel_size_weight * wheel_growth
+            );
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod play_session;
+mod terminal_game;
+
+pub use play_session::*;
+pub use terminal_game::*;
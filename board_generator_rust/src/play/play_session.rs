@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+
+use crate::board::{BoardError, BoardManager, Direction};
+use crate::generator::GeneratedBoard;
+use crate::utils::{shuffle, with_rng, word_length};
+
+///
+/// What happened when the player guessed a word.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuessResult {
+    /// The word is on the board, and has now been revealed
+    Found,
+    /// The word can be built from the letters, but isn't on the board
+    Bonus,
+    /// The word was already found (or was a bonus word already)
+    AlreadyFound,
+    /// The word can't be built from the letters
+    NotAWord,
+}
+
+///
+/// The state of a single game being played on a generated board.
+/// This is only the game logic (guesses, hints, ...), so it can be
+/// driven by the terminal, or by tests.
+///
+#[derive(Debug)]
+pub struct PlaySession {
+    bm: BoardManager,
+    /// The cells of each word placed on the board
+    word_cells: Vec<(String, Vec<(usize, usize)>)>,
+    /// Words which can be built from the letters, but aren't placed
+    bonus_words: HashSet<String>,
+    found_words: Vec<String>,
+    found_bonus_words: Vec<String>,
+    revealed_cells: HashSet<(usize, usize)>,
+    wheel: Vec<char>,
+    number_of_guesses: usize,
+    number_of_hints: usize,
+}
+
+// ===============Static Methods=================
+
+impl PlaySession {
+    ///
+    /// Starts a new game on a previously generated board.
+    /// Fails if the board isn't valid.
+    ///
+    pub fn new(board: &GeneratedBoard) -> Result<Self, BoardError> {
+        let bm = BoardManager::from_generated_board(board)?;
+
+        let mut word_cells = Vec::new();
+        for (word, location) in board.used.iter() {
+            let cells = (0..word_length(word))
+                .map(|offset| match location.direction {
+                    Direction::H => (location.row, location.col + offset),
+                    Direction::V => (location.row + offset, location.col),
+                })
+                .collect();
+            word_cells.push((word.to_owned(), cells));
+        }
+        word_cells.sort();
+
+        let bonus_words = board
+            .all
+            .split('_')
+            .filter(|word| !word.is_empty() && !board.used.contains_key(*word))
+            .map(|word| word.to_owned())
+            .collect();
+
+        Ok(Self {
+            bm,
+            word_cells,
+            bonus_words,
+            found_words: Vec::new(),
+            found_bonus_words: Vec::new(),
+            revealed_cells: HashSet::new(),
+            wheel: shuffle(&board.letters.chars().collect::<Vec<char>>()),
+            number_of_guesses: 0,
+            number_of_hints: 0,
+        })
+    }
+}
+
+// ===============Instance Methods===============
+
+impl PlaySession {
+    ///
+    /// The player types in a word. If it's on the board,
+    /// all of its letters get revealed.
+    ///
+    pub fn guess(&mut self, word: &str) -> GuessResult {
+        let word = word.trim().to_lowercase();
+        self.number_of_guesses += 1;
+
+        if self.found_words.contains(&word)
+            || self.found_bonus_words.contains(&word)
+        {
+            return GuessResult::AlreadyFound;
+        }
+
+        if let Some((_, cells)) = self
+            .word_cells
+            .iter()
+            .find(|(placed_word, _)| *placed_word == word)
+        {
+            self.revealed_cells.extend(cells.iter().copied());
+            self.update_found_words();
+            return GuessResult::Found;
+        }
+
+        if self.bonus_words.contains(&word) {
+            self.found_bonus_words.push(word);
+            return GuessResult::Bonus;
+        }
+
+        GuessResult::NotAWord
+    }
+
+    ///
+    /// Reveals one (random) letter of a word that hasn't been found.
+    /// Returns the (row, col) of the revealed cell, or `None` when
+    /// every letter has already been revealed.
+    ///
+    pub fn hint(&mut self) -> Option<(usize, usize)> {
+        let hidden_cells: Vec<(usize, usize)> = self
+            .word_cells
+            .iter()
+            .flat_map(|(_, cells)| cells.iter().copied())
+            .filter(|cell| !self.revealed_cells.contains(cell))
+            .collect();
+        let hidden_cell = with_rng(|rng| hidden_cells.choose(rng).copied())?;
+
+        self.number_of_hints += 1;
+        self.revealed_cells.insert(hidden_cell);
+        self.update_found_words();
+
+        Some(hidden_cell)
+    }
+
+    ///
+    /// Shuffles the order of the letters on the wheel
+    ///
+    pub fn shuffle_wheel(&mut self) {
+        self.wheel = shuffle(&self.wheel);
+    }
+
+    pub fn get_wheel(&self) -> String {
+        self.wheel.iter().collect()
+    }
+
+    ///
+    /// The grid, the way the player sees it. Only the
+    /// revealed letters are shown.
+    ///
+    pub fn render_grid(&self) -> String {
+        self.bm
+            .render_grid(|row, col| self.revealed_cells.contains(&(row, col)))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.found_words.len() == self.word_cells.len()
+    }
+
+    ///
+    /// A summary of the game so far, for the end of the game.
+    ///
+    pub fn get_summary(&self) -> String {
+        let unfound_words: Vec<&String> = self
+            .word_cells
+            .iter()
+            .map(|(word, _)| word)
+            .filter(|word| !self.found_words.contains(word))
+            .collect();
+
+        format!(
+            "Found {} of {} words, and {} of {} bonus words.\n\
+             Guesses: {}, Hints: {}\n\
+             Bonus words found: {:?}\n\
+             Words not found: {:?}",
+            self.found_words.len(),
+            self.word_cells.len(),
+            self.found_bonus_words.len(),
+            self.bonus_words.len(),
+            self.number_of_guesses,
+            self.number_of_hints,
+            self.found_bonus_words,
+            unfound_words,
+        )
+    }
+
+    ///
+    /// A word is found once all of its letters are revealed, whether
+    /// the player typed it in, or revealed it with crossing words
+    /// and hints.
+    ///
+    fn update_found_words(&mut self) {
+        for (word, cells) in self.word_cells.iter() {
+            if !self.found_words.contains(word)
+                && cells.iter().all(|cell| self.revealed_cells.contains(cell))
+            {
+                self.found_words.push(word.to_owned());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_misuses_board;
+    use crate::utils::set_random_seed;
+
+    #[test]
+    fn test_guesses() {
+        let mut session = PlaySession::new(&make_misuses_board()).unwrap();
+
+        assert_eq!(session.guess("MESS"), GuessResult::Found);
+        assert_eq!(session.guess("mess"), GuessResult::AlreadyFound);
+        assert_eq!(session.guess("muse"), GuessResult::Bonus);
+        assert_eq!(session.guess("muse"), GuessResult::AlreadyFound);
+        assert_eq!(session.guess("moss"), GuessResult::NotAWord);
+        assert!(!session.is_complete());
+
+        assert_eq!(session.guess("misuses"), GuessResult::Found);
+        assert!(session.is_complete());
+        assert!(session.render_grid().contains("m i s u s e s"));
+    }
+
+    #[test]
+    fn test_hints_reveal_words() {
+        let mut session = PlaySession::new(&make_misuses_board()).unwrap();
+        session.guess("misuses");
+
+        // "mess" has 3 letters left to reveal
+        for _ in 0..3 {
+            assert!(session.hint().is_some());
+        }
+        assert!(session.hint().is_none());
+        assert!(session.is_complete());
+    }
+
+    #[test]
+    fn test_hints_follow_the_random_seed() {
+        let get_hints = || {
+            set_random_seed(Some(7));
+            let mut session = PlaySession::new(&make_misuses_board()).unwrap();
+            std::iter::from_fn(|| session.hint()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(get_hints(), get_hints());
+        set_random_seed(None);
+    }
+}
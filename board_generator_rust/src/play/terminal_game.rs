@@ -0,0 +1,71 @@
+use std::io::{self, BufRead, Write};
+
+use colored::Colorize;
+
+use super::{GuessResult, PlaySession};
+use crate::generator::GeneratedBoard;
+
+/// Typed in by the player instead of a guess
+const HINT_COMMAND: &str = "/hint";
+const SHUFFLE_COMMAND: &str = "/shuffle";
+const QUIT_COMMAND: &str = "/quit";
+
+///
+/// Plays a board in the terminal. The player types in guesses (one
+/// per line), or one of the commands, until every word on the board
+/// has been found, or they quit. A summary is printed at the end.
+///
+pub fn play_in_terminal(board: &GeneratedBoard) {
+    let mut session = match PlaySession::new(board) {
+        Ok(session) => session,
+        Err(error) => {
+            println!("This board can't be played: {error}");
+            return;
+        }
+    };
+
+    println!(
+        "Find the words! Type a guess, or {HINT_COMMAND}, \
+         {SHUFFLE_COMMAND}, {QUIT_COMMAND}"
+    );
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    while !session.is_complete() {
+        println!("\n{}", session.render_grid());
+        print!("Letters: {} > ", session.get_wheel().to_uppercase().bold());
+        io::stdout()
+            .flush()
+            .expect("Could not write to the terminal.");
+
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+
+        match line.trim() {
+            "" => continue,
+            QUIT_COMMAND => break,
+            SHUFFLE_COMMAND => session.shuffle_wheel(),
+            HINT_COMMAND => match session.hint() {
+                Some((row, col)) => {
+                    println!("Revealed the letter at row {row}, column {col}.")
+                }
+                None => println!("There's nothing left to reveal."),
+            },
+            guess => match session.guess(guess) {
+                GuessResult::Found => println!("{}", "Found it!".green()),
+                GuessResult::Bonus => println!("{}", "Bonus word!".cyan()),
+                GuessResult::AlreadyFound => {
+                    println!("{}", "Already found.".yellow())
+                }
+                GuessResult::NotAWord => println!("{}", "Not a word.".red()),
+            },
+        }
+    }
+
+    if session.is_complete() {
+        println!("\n{}", session.render_grid());
+        println!("{}", "You found every word!".green().bold());
+    }
+    println!("\n{}", session.get_summary());
+}
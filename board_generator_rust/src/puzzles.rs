@@ -0,0 +1,5 @@
+mod codeword;
+mod word_search;
+
+pub use codeword::*;
+pub use word_search::*;
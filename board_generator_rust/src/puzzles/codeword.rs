@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::board::{BoardError, BoardManager, EMPTY_CELL_VALUE};
+use crate::config::CodewordConfig;
+use crate::dictionary::DictionaryManager;
+use crate::generator::GeneratedBoard;
+use crate::utils::shuffle;
+
+/// The number of an empty cell, in the numbered grid
+pub const CODEWORD_EMPTY_CELL: u32 = 0;
+
+/// The dictionary's words (as letters), by their length
+pub type WordsByLength = HashMap<usize, Vec<Vec<char>>>;
+
+///
+/// A codeword puzzle. Every letter of the grid is replaced by a
+/// number, and the same letter always has the same number. The
+/// player starts with a few letters revealed (the starters).
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CodewordPuzzle {
+    // Each row of the grid, as numbers. Empty cells are 0.
+    pub grid: Vec<Vec<u32>>,
+    // The letters revealed to the player, by their number
+    pub starters: BTreeMap<u32, char>,
+    // Every letter of the grid, by its number
+    pub key: BTreeMap<u32, char>,
+    // The language code of the dictionary the words came from (Ex: "en")
+    pub language: String,
+}
+
+///
+/// Why a board can't be turned into a codeword puzzle.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodewordError {
+    /// The board's grid doesn't agree with its words
+    InvalidBoard(BoardError),
+    /// More starters than the config allows are needed for
+    /// the solution to be unique
+    TooManyStarters { max_starters: usize },
+}
+
+impl fmt::Display for CodewordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodewordError::InvalidBoard(error) => {
+                write!(f, "the board is not valid: {error}")
+            }
+            CodewordError::TooManyStarters { max_starters } => write!(
+                f,
+                "more than {max_starters} starters are needed to solve it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CodewordError {}
+
+///
+/// Turns a generated board into a codeword puzzle. The letters are
+/// numbered at random, and then the fewest starters are chosen, for
+/// which the puzzle can be solved by deduction alone.
+///
+/// A puzzle which can be solved by deduction (without ever guessing)
+/// only has one solution. See `solve_codeword()`.
+///
+/// Example:
+/// ```
+/// let boards = read_generated_boards("./boards.json");
+/// // Ex: { 3: 'e' }
+/// generate_codeword(&boards[0], &dictionary, &all_config.codeword)?.starters;
+/// ```
+///
+pub fn generate_codeword(
+    board: &GeneratedBoard,
+    dictionary: &DictionaryManager,
+    config: &CodewordConfig,
+) -> Result<CodewordPuzzle, CodewordError> {
+    let bm = BoardManager::from_generated_board(board)
+        .map_err(CodewordError::InvalidBoard)?;
+
+    let letters: Vec<char> = board
+        .grid
+        .chars()
+        .filter(|letter| *letter != '_' && *letter != EMPTY_CELL_VALUE)
+        .unique()
+        .sorted()
+        .collect();
+    let numbers: HashMap<char, u32> =
+        shuffle(&letters).into_iter().zip(1..).collect();
+    let key: BTreeMap<u32, char> = numbers
+        .iter()
+        .map(|(letter, number)| (*number, *letter))
+        .collect();
+
+    let grid: Vec<Vec<u32>> = board
+        .grid
+        .split('_')
+        .map(|row| {
+            row.chars()
+                .map(|letter| match numbers.get(&letter) {
+                    Some(number) => *number,
+                    None => CODEWORD_EMPTY_CELL,
+                })
+                .collect()
+        })
+        .collect();
+    let slots: Vec<Vec<u32>> = bm
+        .get_used_word_objects()
+        .into_iter()
+        .sorted_by(|a, b| a.word.cmp(&b.word))
+        .map(|placed_word| {
+            placed_word
+                .cells
+                .iter()
+                .map(|cell| grid[cell.row][cell.col])
+                .collect()
+        })
+        .collect();
+    let words_by_length = get_words_by_length(&slots, dictionary);
+
+    // Every combination of `n` starters is tried, before trying `n + 1`
+    for number_of_starters in 0..=config.max_starters.min(key.len()) {
+        for starters in key.keys().combinations(number_of_starters) {
+            let starters: BTreeMap<u32, char> = starters
+                .into_iter()
+                .map(|number| (*number, key[number]))
+                .collect();
+            let solved = solve_codeword(&slots, &starters, &words_by_length);
+            if solved.len() == key.len() {
+                return Ok(CodewordPuzzle {
+                    grid,
+                    starters,
+                    key,
+                    language: board.language.to_owned(),
+                });
+            }
+        }
+    }
+
+    Err(CodewordError::TooManyStarters {
+        max_starters: config.max_starters,
+    })
+}
+
+///
+/// Solves a codeword the way the player would. For each word (slot) of
+/// the grid, every dictionary word which fits the numbers is listed.
+/// When all of them agree on a number's letter, that letter must be
+/// right. This repeats until nothing new can be deduced.
+///
+/// Returns every number whose letter was deduced (including the
+/// starters). It's only deduction, so nothing is ever wrong, but
+/// the puzzle is only solved if every number is there.
+///
+/// `words_by_length` must have the words of every slot's length. See
+/// `get_words_by_length()`.
+///
+pub fn solve_codeword(
+    slots: &[Vec<u32>],
+    starters: &BTreeMap<u32, char>,
+    words_by_length: &WordsByLength,
+) -> BTreeMap<u32, char> {
+    let mut known = starters.clone();
+
+    loop {
+        let mut has_deduced = false;
+        for slot in slots.iter() {
+            let candidates: Vec<&Vec<char>> = words_by_length[&slot.len()]
+                .iter()
+                .filter(|word| fits_numbers(word, slot, &known))
+                .collect();
+            let Some(first_candidate) = candidates.first() else {
+                continue;
+            };
+            for (position, number) in slot.iter().enumerate() {
+                let letter = first_candidate[position];
+                if !known.contains_key(number)
+                    && candidates.iter().all(|word| word[position] == letter)
+                {
+                    known.insert(*number, letter);
+                    has_deduced = true;
+                }
+            }
+        }
+        if !has_deduced {
+            return known;
+        }
+    }
+}
+
+///
+/// Lists the dictionary's words of each slot's length. This is built
+/// once per board, since every combination of starters is solved with
+/// the same words.
+///
+pub fn get_words_by_length(
+    slots: &[Vec<u32>],
+    dictionary: &DictionaryManager,
+) -> WordsByLength {
+    slots
+        .iter()
+        .map(|slot| slot.len())
+        .unique()
+        .map(|length| {
+            let words = dictionary
+                .get_all_words_of_length(length)
+                .map(|words| {
+                    words.iter().map(|word| word.chars().collect()).collect()
+                })
+                .unwrap_or_default();
+            (length, words)
+        })
+        .collect()
+}
+
+///
+/// Checks if a word could be spelled by the numbers. The same number
+/// is always the same letter, different numbers are always different
+/// letters, and the known numbers must have their known letter.
+///
+fn fits_numbers(
+    word: &[char],
+    numbers: &[u32],
+    known: &BTreeMap<u32, char>,
+) -> bool {
+    let mut letters_of_numbers: HashMap<u32, char> = HashMap::new();
+    word.iter().zip(numbers.iter()).all(|(letter, number)| {
+        let fits_known = match known.get(number) {
+            Some(known_letter) => known_letter == letter,
+            // The letter already belongs to another number
+            None => !known.values().any(|known_letter| known_letter == letter),
+        };
+        let fits_word = match letters_of_numbers.insert(*number, *letter) {
+            Some(previous_letter) => previous_letter == *letter,
+            None => true,
+        } && letters_of_numbers.iter().all(
+            |(other, other_letter)| other == number || other_letter != letter,
+        );
+        fits_known && fits_word
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::config::Config;
+    use crate::test_utils::{make_board, make_dictionary};
+
+    #[test]
+    fn test_generate_codeword() {
+        let board = GeneratedBoard {
+            letters: String::from("seat"),
+            ..make_board(
+                5,
+                &[("sat", 0, 0, Direction::H), ("tea", 0, 2, Direction::V)],
+            )
+        };
+        let dictionary = make_dictionary("tests rests sat tea set sea eat toe");
+
+        let puzzle =
+            generate_codeword(&board, &dictionary, &Config::default().codeword)
+                .unwrap();
+
+        // Decoding the grid with the key gives back the board
+        let decoded = puzzle
+            .grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|number| match puzzle.key.get(number) {
+                        Some(letter) => *letter,
+                        None => EMPTY_CELL_VALUE,
+                    })
+                    .collect::<String>()
+            })
+            .join("_");
+        assert_eq!(decoded, board.grid);
+        assert_eq!(puzzle.key.len(), 4);
+        // Every 3-letter word fits each slot, until one letter is known
+        assert_eq!(puzzle.starters.len(), 1);
+        let slots = [vec![1, 2, 3], vec![3, 4, 2]];
+        let words_by_length = get_words_by_length(&slots, &dictionary);
+        assert!(solve_codeword(&slots, &BTreeMap::new(), &words_by_length)
+            .is_empty());
+        assert_eq!(
+            solve_codeword(
+                &slots,
+                &BTreeMap::from([(2, 'a')]),
+                &words_by_length
+            ),
+            BTreeMap::from([(1, 's'), (2, 'a'), (3, 't'), (4, 'e')])
+        );
+    }
+
+    #[test]
+    fn test_fits_numbers() {
+        let known = BTreeMap::from([(1, 't')]);
+        let tests: Vec<char> = "tests".chars().collect();
+        let rests: Vec<char> = "rests".chars().collect();
+
+        assert!(fits_numbers(&tests, &[1, 2, 3, 1, 3], &known));
+        assert!(!fits_numbers(&rests, &[1, 2, 3, 1, 3], &known));
+        // 'e' and 's' can't both be number 2
+        assert!(!fits_numbers(&tests, &[1, 2, 2, 1, 3], &known));
+        // 't' is already number 1
+        assert!(!fits_numbers(&tests, &[4, 2, 3, 4, 3], &known));
+    }
+}
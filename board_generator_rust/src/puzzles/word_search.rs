@@ -0,0 +1,467 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::board::EMPTY_CELL_VALUE;
+use crate::config::WordSearchConfig;
+use crate::dictionary::DictionaryManager;
+use crate::utils::{get_random_int_in_range, shuffle, word_length};
+
+///
+/// The 8 directions a word can be hidden in. Each direction
+/// is the way the word reads, from its first letter.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WordSearchDirection {
+    #[serde(rename = "r")]
+    Right,
+    #[serde(rename = "l")]
+    Left,
+    #[serde(rename = "d")]
+    Down,
+    #[serde(rename = "u")]
+    Up,
+    #[serde(rename = "dr")]
+    DownRight,
+    #[serde(rename = "ul")]
+    UpLeft,
+    #[serde(rename = "dl")]
+    DownLeft,
+    #[serde(rename = "ur")]
+    UpRight,
+}
+
+impl WordSearchDirection {
+    pub const ALL: [WordSearchDirection; 8] = [
+        WordSearchDirection::Right,
+        WordSearchDirection::Left,
+        WordSearchDirection::Down,
+        WordSearchDirection::Up,
+        WordSearchDirection::DownRight,
+        WordSearchDirection::UpLeft,
+        WordSearchDirection::DownLeft,
+        WordSearchDirection::UpRight,
+    ];
+
+    ///
+    /// How far (rows, columns) the next letter of the word is.
+    ///
+    pub fn step(&self) -> (isize, isize) {
+        match self {
+            WordSearchDirection::Right => (0, 1),
+            WordSearchDirection::Left => (0, -1),
+            WordSearchDirection::Down => (1, 0),
+            WordSearchDirection::Up => (-1, 0),
+            WordSearchDirection::DownRight => (1, 1),
+            WordSearchDirection::UpLeft => (-1, -1),
+            WordSearchDirection::DownLeft => (1, -1),
+            WordSearchDirection::UpRight => (-1, 1),
+        }
+    }
+
+    ///
+    /// Words which read right to left, or bottom to top.
+    ///
+    pub fn is_reversed(&self) -> bool {
+        let (row_step, col_step) = self.step();
+        col_step < 0 || (col_step == 0 && row_step < 0)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (row_step, col_step) = self.step();
+        row_step != 0 && col_step != 0
+    }
+}
+
+///
+/// What a word may do with the letters of words
+/// which were hidden before it.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordSearchOverlap {
+    /// Every word has cells of its own
+    Never,
+    /// Words can cross, where they have the same letter
+    SharedLetters,
+}
+
+///
+/// Because the purpose of this struct is for serialization
+/// the field names are abbreviated, like `WordLocationOnBoard`.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HiddenWord {
+    pub word: String,
+    /// row index of the first letter
+    #[serde(rename = "r")]
+    pub row: usize,
+    /// column index of the first letter
+    #[serde(rename = "c")]
+    pub col: usize,
+    /// direction
+    #[serde(rename = "d")]
+    pub direction: WordSearchDirection,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordSearchPuzzle {
+    // Every row of letters, separated by a "_"
+    pub grid: String,
+    // The letters the hidden words were built from
+    pub letters: String,
+    // The language code of the dictionary the words came from (Ex: "en")
+    pub language: String,
+    // The words to find, and where they're hidden
+    pub words: Vec<HiddenWord>,
+}
+
+///
+/// Builds a word search puzzle, which hides words built from the
+/// letters in a square grid. The words can read in any of the allowed
+/// directions. Once they're hidden, every other cell is filled with
+/// a letter that doesn't spell out any other dictionary (or offensive)
+/// word, in any direction, with the letters around it.
+///
+/// The longest words are hidden first, since they're the hardest
+/// to fit. Returns `None` if fewer than `min_words` could be hidden.
+///
+/// Example:
+/// ```
+/// // Ex: "tsera..._..." with "stare", "tears", "rest", ...
+/// generate_word_search("stare", &dictionary, &all_config.word_search);
+/// ```
+///
+pub fn generate_word_search(
+    letters: &str,
+    dictionary: &DictionaryManager,
+    config: &WordSearchConfig,
+) -> Option<WordSearchPuzzle> {
+    let grid_size = config.grid_size;
+    let directions: Vec<WordSearchDirection> = WordSearchDirection::ALL
+        .into_iter()
+        .filter(|direction| config.allow_reversed || !direction.is_reversed())
+        .filter(|direction| config.allow_diagonal || !direction.is_diagonal())
+        .collect();
+
+    let candidate_words: Vec<String> = shuffle(
+        &dictionary
+            .find_all_words_for_letters(letters, false)
+            .into_iter()
+            .filter(|word| word_length(word) <= grid_size)
+            .collect::<Vec<String>>(),
+    )
+    .into_iter()
+    .sorted_by_key(|word| std::cmp::Reverse(word_length(word)))
+    .collect();
+
+    let mut grid = vec![EMPTY_CELL_VALUE; grid_size * grid_size];
+    let mut words: Vec<HiddenWord> = Vec::new();
+    for word in candidate_words.iter() {
+        if words.len() >= config.max_words {
+            break;
+        }
+        if let Some(hidden_word) =
+            hide_word(&mut grid, grid_size, word, &directions, config)
+        {
+            words.push(hidden_word);
+        }
+    }
+    if words.len() < config.min_words {
+        return None;
+    }
+
+    fill_empty_cells(&mut grid, grid_size, letters, dictionary);
+
+    Some(WordSearchPuzzle {
+        grid: grid
+            .chunks(grid_size)
+            .map(|row| row.iter().collect::<String>())
+            .join("_"),
+        letters: letters.to_owned(),
+        language: dictionary.get_language().code().to_owned(),
+        words,
+    })
+}
+
+///
+/// Tries random positions and directions until the word fits,
+/// according to the overlap rule.
+///
+fn hide_word(
+    grid: &mut [char],
+    grid_size: usize,
+    word: &str,
+    directions: &[WordSearchDirection],
+    config: &WordSearchConfig,
+) -> Option<HiddenWord> {
+    if directions.is_empty() {
+        return None;
+    }
+    let letters: Vec<char> = word.chars().collect();
+
+    for _ in 0..config.max_attempts_per_word {
+        let direction =
+            directions[get_random_int_in_range(0, directions.len())];
+        let row = get_random_int_in_range(0, grid_size);
+        let col = get_random_int_in_range(0, grid_size);
+        let Some(cells) =
+            get_line(grid_size, row, col, direction, letters.len())
+        else {
+            continue;
+        };
+
+        let fits = cells.iter().zip(letters.iter()).all(|(cell, letter)| {
+            grid[*cell] == EMPTY_CELL_VALUE
+                || (config.overlap == WordSearchOverlap::SharedLetters
+                    && grid[*cell] == *letter)
+        });
+        // A word can't be hidden entirely within another word's letters
+        let is_covered =
+            cells.iter().all(|cell| grid[*cell] != EMPTY_CELL_VALUE);
+        if !fits || is_covered {
+            continue;
+        }
+
+        for (cell, letter) in cells.iter().zip(letters.iter()) {
+            grid[*cell] = *letter;
+        }
+        return Some(HiddenWord {
+            word: word.to_owned(),
+            row,
+            col,
+            direction,
+        });
+    }
+
+    None
+}
+
+///
+/// The indexes of the cells of a line, starting at (row, col), if
+/// the whole line fits within the grid.
+///
+fn get_line(
+    grid_size: usize,
+    row: usize,
+    col: usize,
+    direction: WordSearchDirection,
+    length: usize,
+) -> Option<Vec<usize>> {
+    let (row_step, col_step) = direction.step();
+    (0..length as isize)
+        .map(|offset| {
+            let cell_row = row.checked_add_signed(row_step * offset)?;
+            let cell_col = col.checked_add_signed(col_step * offset)?;
+            match cell_row < grid_size && cell_col < grid_size {
+                true => Some(cell_row * grid_size + cell_col),
+                false => None,
+            }
+        })
+        .collect()
+}
+
+///
+/// Fills the empty cells one at a time. The letters of the puzzle
+/// are tried first, so the filler blends in with the hidden words,
+/// and then the rest of the language's alphabet.
+///
+fn fill_empty_cells(
+    grid: &mut [char],
+    grid_size: usize,
+    letters: &str,
+    dictionary: &DictionaryManager,
+) {
+    let language = dictionary.get_language();
+    let alphabet: Vec<char> = ('a'..='z')
+        .chain(language.extra_letters().iter().copied())
+        .collect();
+
+    for index in 0..grid.len() {
+        if grid[index] != EMPTY_CELL_VALUE {
+            continue;
+        }
+        let puzzle_letters: Vec<char> =
+            shuffle(&letters.chars().unique().collect::<Vec<char>>());
+        let other_letters: Vec<char> = shuffle(
+            &alphabet
+                .iter()
+                .copied()
+                .filter(|letter| !puzzle_letters.contains(letter))
+                .collect::<Vec<char>>(),
+        );
+
+        // If every letter spells something, the first one is kept
+        let fill_letter = puzzle_letters
+            .iter()
+            .chain(other_letters.iter())
+            .copied()
+            .find(|letter| {
+                grid[index] = *letter;
+                !spells_a_word(grid, grid_size, index, dictionary)
+            })
+            .or(puzzle_letters.first().copied())
+            .unwrap_or(alphabet[0]);
+        grid[index] = fill_letter;
+    }
+}
+
+///
+/// Checks every run of filled cells, which goes through the cell,
+/// for a dictionary (or offensive) word, reading both ways.
+///
+fn spells_a_word(
+    grid: &[char],
+    grid_size: usize,
+    index: usize,
+    dictionary: &DictionaryManager,
+) -> bool {
+    let (row, col) = (index / grid_size, index % grid_size);
+    // Reading the other way is checked by reversing the run
+    let axes = [
+        WordSearchDirection::Right,
+        WordSearchDirection::Down,
+        WordSearchDirection::DownRight,
+        WordSearchDirection::UpRight,
+    ];
+
+    axes.iter().any(|direction| {
+        let (row_step, col_step) = direction.step();
+        let is_filled = |offset: isize| {
+            let cell_row = row.checked_add_signed(row_step * offset)?;
+            let cell_col = col.checked_add_signed(col_step * offset)?;
+            if cell_row >= grid_size || cell_col >= grid_size {
+                return None;
+            }
+            let letter = grid[cell_row * grid_size + cell_col];
+            (letter != EMPTY_CELL_VALUE).then_some(letter)
+        };
+        // The filled cells before and after the cell
+        let before: Vec<char> = (1..)
+            .map_while(|offset| is_filled(-offset))
+            .collect::<Vec<char>>()
+            .into_iter()
+            .rev()
+            .collect();
+        let after: Vec<char> = (1..).map_while(is_filled).collect();
+        let run: Vec<char> = before
+            .iter()
+            .copied()
+            .chain(std::iter::once(grid[index]))
+            .chain(after.iter().copied())
+            .collect();
+        let position = before.len();
+
+        (0..=position).any(|start| {
+            (position + 1..=run.len()).any(|end| {
+                let forwards: String = run[start..end].iter().collect();
+                let backwards: String = forwards.chars().rev().collect();
+                [forwards, backwards].iter().any(|word| {
+                    dictionary.is_word(word) || dictionary.is_profane(word)
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_utils::make_dictionary;
+    use crate::utils::set_random_seed;
+
+    ///
+    /// Every place (cells and direction) a dictionary word can be read.
+    ///
+    fn find_every_word(
+        grid: &[char],
+        grid_size: usize,
+        dictionary: &DictionaryManager,
+    ) -> Vec<(String, Vec<usize>)> {
+        let mut found = Vec::new();
+        for index in 0..grid.len() {
+            for direction in WordSearchDirection::ALL {
+                for length in 2..=grid_size {
+                    let Some(cells) = get_line(
+                        grid_size,
+                        index / grid_size,
+                        index % grid_size,
+                        direction,
+                        length,
+                    ) else {
+                        continue;
+                    };
+                    let word: String = cells.iter().map(|c| grid[*c]).collect();
+                    if dictionary.is_word(&word) {
+                        found.push((word, cells));
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_generate_word_search() {
+        let dictionary =
+            make_dictionary("stare tears rest seat rate east star sat");
+        let mut config = Config::default().word_search;
+        config.grid_size = 7;
+        config.min_words = 4;
+        set_random_seed(Some(7));
+
+        let puzzle =
+            generate_word_search("stare", &dictionary, &config).unwrap();
+        set_random_seed(None);
+
+        let grid: Vec<char> = puzzle.grid.replace('_', "").chars().collect();
+        assert_eq!(grid.len(), 49);
+        assert!(!grid.contains(&EMPTY_CELL_VALUE));
+
+        let mut hidden_cells = Vec::new();
+        for hidden_word in puzzle.words.iter() {
+            let cells = get_line(
+                7,
+                hidden_word.row,
+                hidden_word.col,
+                hidden_word.direction,
+                word_length(&hidden_word.word),
+            )
+            .unwrap();
+            let word: String = cells.iter().map(|c| grid[*c]).collect();
+            assert_eq!(word, hidden_word.word);
+            hidden_cells.extend(cells);
+        }
+
+        // The filler never spells a word, so every word in the grid
+        // is made only from the letters of the hidden words
+        for (word, cells) in find_every_word(&grid, 7, &dictionary) {
+            assert!(
+                cells.iter().all(|cell| hidden_cells.contains(cell)),
+                "The filler spells '{word}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_directions() {
+        let straight = WordSearchDirection::ALL
+            .into_iter()
+            .filter(|direction| !direction.is_diagonal())
+            .count();
+        let forwards = WordSearchDirection::ALL
+            .into_iter()
+            .filter(|direction| !direction.is_reversed())
+            .collect::<Vec<_>>();
+
+        assert_eq!(straight, 4);
+        assert_eq!(
+            forwards,
+            vec![
+                WordSearchDirection::Right,
+                WordSearchDirection::Down,
+                WordSearchDirection::DownRight,
+                WordSearchDirection::UpRight,
+            ]
+        );
+    }
+}
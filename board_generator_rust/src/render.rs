@@ -0,0 +1,5 @@
+mod board_layout;
+mod board_svg;
+
+pub use board_layout::*;
+pub use board_svg::*;
@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::board::{Direction, EMPTY_CELL_VALUE};
+use crate::generator::GeneratedBoard;
+use crate::utils::word_length;
+
+///
+/// A generated board, cropped down to the rows and columns that are
+/// actually used, with the information needed to draw it (like a
+/// crossword): which cells cross, and where each word starts.
+///
+#[derive(Clone, Debug, Default)]
+pub struct BoardLayout {
+    pub rows: usize,
+    pub cols: usize,
+    /// The letter of every used cell, keyed by (row, col)
+    pub letters: BTreeMap<(usize, usize), char>,
+    /// How many words use each cell. Cells with 2 are intersections.
+    pub words_per_cell: HashMap<(usize, usize), usize>,
+    /// The crossword style number of each cell where a word starts.
+    /// Cells are numbered from left to right, top to bottom.
+    pub word_numbers: BTreeMap<(usize, usize), usize>,
+    /// Each placed word, with its number and direction
+    pub words: Vec<(usize, Direction, String)>,
+}
+
+impl BoardLayout {
+    pub fn new(board: &GeneratedBoard) -> Self {
+        let grid: Vec<Vec<char>> = board
+            .grid
+            .split('_')
+            .map(|row| row.chars().collect())
+            .collect();
+
+        // Crop off the rows and columns with no letters in them
+        let used_cells: Vec<(usize, usize)> = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row, letters)| {
+                letters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, letter)| **letter != EMPTY_CELL_VALUE)
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect();
+        if used_cells.is_empty() {
+            return Self::default();
+        }
+        let first_row =
+            used_cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+        let last_row =
+            used_cells.iter().map(|(row, _)| *row).max().unwrap_or(0);
+        let first_col =
+            used_cells.iter().map(|(_, col)| *col).min().unwrap_or(0);
+        let last_col =
+            used_cells.iter().map(|(_, col)| *col).max().unwrap_or(0);
+
+        let mut layout = Self {
+            rows: last_row - first_row + 1,
+            cols: last_col - first_col + 1,
+            ..Self::default()
+        };
+        for (row, col) in used_cells {
+            layout
+                .letters
+                .insert((row - first_row, col - first_col), grid[row][col]);
+        }
+
+        let mut word_starts: Vec<((usize, usize), Direction, &String)> =
+            Vec::new();
+        for (word, location) in board.used.iter() {
+            let start = (location.row - first_row, location.col - first_col);
+            for offset in 0..word_length(word) {
+                let cell = match location.direction {
+                    Direction::H => (start.0, start.1 + offset),
+                    Direction::V => (start.0 + offset, start.1),
+                };
+                *layout.words_per_cell.entry(cell).or_insert(0) += 1;
+            }
+            word_starts.push((start, location.direction, word));
+        }
+
+        // Horizontal words are listed before vertical words
+        word_starts.sort_by_key(|(start, direction, _)| {
+            (*start, *direction == Direction::V)
+        });
+        for (start, direction, word) in word_starts {
+            let next_number = layout.word_numbers.len() + 1;
+            let number =
+                *layout.word_numbers.entry(start).or_insert(next_number);
+            layout.words.push((number, direction, word.to_owned()));
+        }
+
+        layout
+    }
+
+    pub fn is_intersection(&self, cell: &(usize, usize)) -> bool {
+        self.words_per_cell
+            .get(cell)
+            .is_some_and(|count| *count > 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_misuses_board;
+
+    #[test]
+    fn test_board_layout() {
+        let board = make_misuses_board();
+        let layout = BoardLayout::new(&board);
+
+        assert_eq!((layout.rows, layout.cols), (4, 7));
+        assert_eq!(layout.letters.len(), 10);
+        assert!(layout.is_intersection(&(0, 0)));
+        assert!(!layout.is_intersection(&(0, 1)));
+        assert_eq!(
+            layout.words,
+            vec![
+                (1, Direction::H, "misuses".to_owned()),
+                (1, Direction::V, "mess".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::f32::consts::PI;
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use itertools::Itertools;
+
+use super::BoardLayout;
+use crate::config::RenderConfig;
+use crate::generator::GeneratedBoard;
+use crate::utils::escape_html;
+
+///
+/// The different ways a board can be drawn
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SvgView {
+    /// Every letter is filled in
+    Solved,
+    /// Only the empty cells, the way the player first sees the board
+    Blank,
+    /// Solved, with the intersections highlighted, and the
+    /// starting cell of each word numbered
+    Annotated,
+    /// Blank, with the starting cell of each word numbered. This
+    /// is the view used for printed puzzles.
+    Puzzle,
+}
+
+impl SvgView {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SvgView::Solved => "solved",
+            SvgView::Blank => "blank",
+            SvgView::Annotated => "annotated",
+            SvgView::Puzzle => "puzzle",
+        }
+    }
+}
+
+///
+/// Draws a generated board as a standalone SVG image. The grid is
+/// cropped to its used cells, and the letter wheel is drawn to the
+/// right of it. The wheel's letters are in alphabetical order, so
+/// the starting word isn't given away.
+///
+pub fn render_board_svg(
+    board: &GeneratedBoard,
+    view: SvgView,
+    render_config: &RenderConfig,
+) -> String {
+    let render_config = &escape_render_config(render_config);
+    let layout = BoardLayout::new(board);
+    let cell_size = render_config.cell_size as f32;
+    let padding = cell_size / 2.0;
+
+    let grid_width = layout.cols as f32 * cell_size;
+    let grid_height = layout.rows as f32 * cell_size;
+    let wheel_radius =
+        (grid_height / 2.0).clamp(cell_size * 1.5, cell_size * 3.0);
+    let width = padding * 3.0 + grid_width + wheel_radius * 2.0;
+    let height = padding * 2.0 + grid_height.max(wheel_radius * 2.0);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}" font-family="{}">"#,
+        render_config.font_family
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        render_config.background_color
+    );
+
+    // ===============The Grid===============
+    for ((row, col), letter) in layout.letters.iter() {
+        let x = padding + *col as f32 * cell_size;
+        let y = padding + *row as f32 * cell_size;
+        let fill = match view == SvgView::Annotated
+            && layout.is_intersection(&(*row, *col))
+        {
+            true => &render_config.intersection_color,
+            false => &render_config.cell_color,
+        };
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x:.1}" y="{y:.1}" width="{cell_size:.1}" height="{cell_size:.1}" fill="{fill}" stroke="{}" stroke-width="2"/>"#,
+            render_config.line_color
+        );
+
+        if view == SvgView::Annotated || view == SvgView::Puzzle {
+            if let Some(number) = layout.word_numbers.get(&(*row, *col)) {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" fill="{}">{number}</text>"#,
+                    x + cell_size * 0.08,
+                    y + cell_size * 0.28,
+                    cell_size * 0.25,
+                    render_config.number_color
+                );
+            }
+        }
+
+        if view == SvgView::Solved || view == SvgView::Annotated {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                x + cell_size / 2.0,
+                y + cell_size / 2.0,
+                cell_size * 0.6,
+                render_config.letter_color,
+                escape_html(&letter.to_uppercase().to_string())
+            );
+        }
+    }
+
+    // ===============The Letter Wheel===============
+    let wheel_x = padding * 2.0 + grid_width + wheel_radius;
+    let wheel_y = height / 2.0;
+    let _ = writeln!(
+        svg,
+        r#"<circle cx="{wheel_x:.1}" cy="{wheel_y:.1}" r="{wheel_radius:.1}" fill="{}"/>"#,
+        render_config.wheel_color
+    );
+    let wheel_letters: Vec<char> = board.letters.chars().sorted().collect();
+    for (index, letter) in wheel_letters.iter().enumerate() {
+        // Start at the top of the wheel, and go clockwise
+        let angle =
+            2.0 * PI * index as f32 / wheel_letters.len() as f32 - PI / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            wheel_x + wheel_radius * 0.7 * angle.cos(),
+            wheel_y + wheel_radius * 0.7 * angle.sin(),
+            cell_size * 0.5,
+            render_config.wheel_letter_color,
+            escape_html(&letter.to_uppercase().to_string())
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+///
+/// The font and colors are written into the SVG's attributes as they
+/// are, so any quotes or brackets in them have to be escaped first.
+///
+fn escape_render_config(render_config: &RenderConfig) -> RenderConfig {
+    RenderConfig {
+        font_family: escape_html(&render_config.font_family),
+        background_color: escape_html(&render_config.background_color),
+        cell_color: escape_html(&render_config.cell_color),
+        line_color: escape_html(&render_config.line_color),
+        letter_color: escape_html(&render_config.letter_color),
+        intersection_color: escape_html(&render_config.intersection_color),
+        number_color: escape_html(&render_config.number_color),
+        wheel_color: escape_html(&render_config.wheel_color),
+        wheel_letter_color: escape_html(&render_config.wheel_letter_color),
+        ..render_config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_utils::make_misuses_board;
+
+    #[test]
+    fn test_render_board_svg() {
+        let board = make_misuses_board();
+        let render_config = Config::default().render;
+        let count = |svg: &str, pattern: &str| svg.matches(pattern).count();
+
+        let blank = render_board_svg(&board, SvgView::Blank, &render_config);
+        let solved = render_board_svg(&board, SvgView::Solved, &render_config);
+        let annotated =
+            render_board_svg(&board, SvgView::Annotated, &render_config);
+
+        // Every view has the 10 cells and the 7 letters of the wheel
+        assert_eq!(count(&blank, "<text"), 7);
+        assert_eq!(count(&solved, "<text"), 17);
+        assert_eq!(count(&annotated, "<text"), 18);
+        assert_eq!(count(&annotated, &render_config.intersection_color), 1);
+        let puzzle = render_board_svg(&board, SvgView::Puzzle, &render_config);
+        assert_eq!(count(&puzzle, "<text"), 8);
+        assert!(blank.starts_with("<svg") && blank.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_render_board_svg_escapes_config() {
+        let mut render_config = Config::default().render;
+        render_config.font_family = r#""Comic Sans", sans-serif"#.to_owned();
+        render_config.cell_color = "<red>".to_owned();
+
+        let svg = render_board_svg(
+            &make_misuses_board(),
+            SvgView::Blank,
+            &render_config,
+        );
+
+        assert!(
+            svg.contains(r#"font-family="&quot;Comic Sans&quot;, sans-serif""#)
+        );
+        assert!(svg.contains(r#"fill="&lt;red&gt;""#));
+        assert!(!svg.contains("<red>"));
+    }
+}
@@ -0,0 +1,5 @@
+mod dictionary_audit;
+mod dictionary_diff;
+
+pub use dictionary_audit::*;
+pub use dictionary_diff::*;
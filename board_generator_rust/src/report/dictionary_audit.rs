@@ -0,0 +1,425 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use colored::Colorize;
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::dictionary::{DictionaryManager, Language};
+use crate::utils::word_length;
+
+/// A pair of letters found in fewer than this many words is "rare"
+const RARE_BIGRAM_THRESHOLD: usize = 5;
+
+/// The width of each bucket of the subword count histogram
+const HISTOGRAM_BUCKET_SIZE: usize = 10;
+
+/// Suffixes used to tell if a word has any related words
+/// (plurals, past tense...) in the dictionary.
+const FAMILY_SUFFIXES: [&str; 13] = [
+    "s", "es", "ed", "ing", "er", "ers", "ly", "d", "ish", "ism", "ist", "ian",
+    "ic",
+];
+
+/// Common endings of (english) names and places. Ex: "boston"
+const NAME_SUFFIXES: [&str; 16] = [
+    "ton", "son", "ville", "burg", "berg", "ford", "field", "stan", "shire",
+    "wick", "ham", "ski", "ov", "ev", "stein", "dorf",
+];
+
+///
+/// The reasons a word in the dictionary might not belong there.
+/// These are all guesses, and need a human to look them over.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuspicionReason {
+    /// No a, e, i, o, or u. Ex: "crypt"
+    NoVowels,
+    /// The same letter three times in a row. Ex: "xxiii"
+    TripleLetter,
+    /// Contains a pair of letters that hardly any other word has
+    RareBigram(String),
+    /// A roman numeral, or no vowels at all (not even "y"). Ex: "kcmg"
+    LikelyAbbreviation,
+    /// Has no related words in the dictionary, and ends like
+    /// a name or a place. Ex: "houston"
+    LikelyProperNoun,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SuspiciousWord {
+    pub word: String,
+    pub reasons: Vec<SuspicionReason>,
+}
+
+///
+/// How many subwords each of the precomputed starting words has.
+///
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SubwordCountDistribution {
+    pub min: usize,
+    pub median: usize,
+    pub mean: f32,
+    pub max: usize,
+    /// The start of each bucket (0, 10, 20...), mapped to the
+    /// number of starting words within that bucket.
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+///
+/// A report over the clean dictionary and the precomputed words map,
+/// which should be looked over before shipping a dictionary rebuild.
+///
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DictionaryAudit {
+    pub total_words: usize,
+    pub words_per_length: BTreeMap<usize, usize>,
+    pub total_starting_words: usize,
+    pub subword_counts: SubwordCountDistribution,
+    pub min_words_to_consider_letters: usize,
+    /// Seven-letter words which can't build enough words to be used as
+    /// starting words, mapped to how many words they can build.
+    pub starting_words_below_minimum: BTreeMap<String, usize>,
+    /// Starting words in the precomputed map which either aren't in the
+    /// clean dictionary, or can't build enough words anymore. The map
+    /// needs to be rebuilt.
+    pub stale_starting_words: Vec<String>,
+    pub suspicious_words: Vec<SuspiciousWord>,
+}
+
+// ===============Static Methods=================
+
+impl DictionaryAudit {
+    ///
+    /// Audits the clean dictionary words, and the precomputed
+    /// map of starting words to their subwords.
+    ///
+    pub fn new(
+        clean_words: &[String],
+        precomputed_words: &HashMap<String, Vec<String>>,
+        min_words_to_consider_letters: usize,
+        language: Language,
+    ) -> Self {
+        let mut audit = Self {
+            total_words: clean_words.len(),
+            total_starting_words: precomputed_words.len(),
+            min_words_to_consider_letters,
+            ..Self::default()
+        };
+
+        for word in clean_words.iter() {
+            *audit.words_per_length.entry(word_length(word)).or_insert(0) += 1;
+        }
+
+        audit.subword_counts = get_subword_count_distribution(
+            precomputed_words.values().map(|subwords| subwords.len()),
+        );
+
+        // Every seven-letter word, and how many words it can build
+        let mut dictionary = DictionaryManager::new();
+        dictionary.load_words(clean_words.to_vec());
+        for seven_letter_word in dictionary
+            .get_all_words_of_length(7)
+            .iter()
+            .flat_map(|words| words.iter())
+        {
+            let number_of_subwords = dictionary
+                .find_all_words_for_letters(seven_letter_word, true)
+                .iter()
+                .filter(|word| *word != seven_letter_word)
+                .count();
+            if number_of_subwords < min_words_to_consider_letters {
+                audit
+                    .starting_words_below_minimum
+                    .insert(seven_letter_word.to_owned(), number_of_subwords);
+            }
+        }
+
+        let all_words: HashSet<&String> = clean_words.iter().collect();
+        audit.stale_starting_words = precomputed_words
+            .keys()
+            .filter(|starting_word| {
+                !all_words.contains(starting_word)
+                    || audit
+                        .starting_words_below_minimum
+                        .contains_key(*starting_word)
+            })
+            .cloned()
+            .collect();
+        audit.stale_starting_words.sort();
+
+        audit.suspicious_words = find_suspicious_words(clean_words, language);
+
+        audit
+    }
+}
+
+// ===============Instance Methods===============
+
+impl DictionaryAudit {
+    ///
+    /// Prints the report as a (human readable) table
+    ///
+    pub fn print_table(&self) {
+        let blank_line = "                                       ".on_cyan();
+        println!("{blank_line}");
+        println!("{}", "           Dictionary Audit            ".on_cyan());
+        println!("{blank_line}");
+
+        println!("{}", "Words Per Length:".bold());
+        println!("{:>8} | {:>8}", "Length", "Words");
+        for (length, count) in self.words_per_length.iter() {
+            println!("{length:>8} | {count:>8}");
+        }
+        println!("{:>8} | {:>8}", "Total", self.total_words);
+        println!("{blank_line}");
+
+        println!("{}", "Subwords Per Starting Word:".bold());
+        let distribution = &self.subword_counts;
+        println!(
+            "{} starting words. Min {}, Median {}, Mean {:.1}, Max {}",
+            self.total_starting_words,
+            distribution.min,
+            distribution.median,
+            distribution.mean,
+            distribution.max
+        );
+        println!("{:>8} | {:>8}", "Subwords", "Starting");
+        for (bucket, count) in distribution.histogram.iter() {
+            let bucket_range =
+                format!("{bucket}-{}", bucket + HISTOGRAM_BUCKET_SIZE - 1);
+            println!("{bucket_range:>8} | {count:>8}");
+        }
+        println!("{blank_line}");
+
+        println!(
+            "{} seven-letter words build fewer than {} words.",
+            self.starting_words_below_minimum.len().to_string().yellow(),
+            self.min_words_to_consider_letters
+        );
+        if self.stale_starting_words.is_empty() {
+            println!("The precomputed words file is up to date.");
+        } else {
+            println!(
+                "{} stale starting words, rebuild the precomputed words file: {:?}",
+                self.stale_starting_words.len().to_string().red(),
+                self.stale_starting_words
+            );
+        }
+        println!("{blank_line}");
+
+        println!(
+            "{} ({})",
+            "Suspicious Words:".bold(),
+            self.suspicious_words.len()
+        );
+        for suspicious_word in self.suspicious_words.iter() {
+            println!(
+                "{:>8} | {:?}",
+                suspicious_word.word, suspicious_word.reasons
+            );
+        }
+        println!("{blank_line}");
+    }
+}
+
+// ===============Audit Utilities===============
+
+fn get_subword_count_distribution(
+    subword_counts: impl Iterator<Item = usize>,
+) -> SubwordCountDistribution {
+    let mut subword_counts: Vec<usize> = subword_counts.collect();
+    if subword_counts.is_empty() {
+        return SubwordCountDistribution::default();
+    }
+    subword_counts.sort();
+
+    let mut histogram = BTreeMap::new();
+    for count in subword_counts.iter() {
+        let bucket = count / HISTOGRAM_BUCKET_SIZE * HISTOGRAM_BUCKET_SIZE;
+        *histogram.entry(bucket).or_insert(0) += 1;
+    }
+
+    SubwordCountDistribution {
+        min: subword_counts[0],
+        median: subword_counts[subword_counts.len() / 2],
+        mean: subword_counts.iter().sum::<usize>() as f32
+            / subword_counts.len() as f32,
+        max: subword_counts[subword_counts.len() - 1],
+        histogram,
+    }
+}
+
+///
+/// Flags every word which looks like it doesn't belong in the
+/// dictionary. See `SuspicionReason`.
+///
+fn find_suspicious_words(
+    clean_words: &[String],
+    language: Language,
+) -> Vec<SuspiciousWord> {
+    let all_words: HashSet<&str> =
+        clean_words.iter().map(|word| word.as_str()).collect();
+
+    // How many words each pair of letters appears in
+    let mut bigram_counts: HashMap<(char, char), usize> = HashMap::new();
+    for word in clean_words.iter() {
+        let bigrams: HashSet<(char, char)> = get_bigrams(word).collect();
+        for bigram in bigrams {
+            *bigram_counts.entry(bigram).or_insert(0) += 1;
+        }
+    }
+
+    clean_words
+        .iter()
+        .filter_map(|word| {
+            let mut reasons = Vec::new();
+
+            let has_vowels = word.chars().any(|letter| is_vowel(letter, false));
+            let has_vowels_or_y =
+                word.chars().any(|letter| is_vowel(letter, true));
+            if !has_vowels {
+                reasons.push(SuspicionReason::NoVowels);
+            }
+
+            let letters: Vec<char> = word.chars().collect();
+            if letters.windows(3).any(|w| w[0] == w[1] && w[1] == w[2]) {
+                reasons.push(SuspicionReason::TripleLetter);
+            }
+
+            if let Some((first, second)) = get_bigrams(word)
+                .find(|bigram| bigram_counts[bigram] < RARE_BIGRAM_THRESHOLD)
+            {
+                reasons.push(SuspicionReason::RareBigram(format!(
+                    "{first}{second}"
+                )));
+            }
+
+            if !has_vowels_or_y || is_roman_numeral(word) {
+                reasons.push(SuspicionReason::LikelyAbbreviation);
+            }
+
+            if language == Language::English
+                && NAME_SUFFIXES.iter().any(|suffix| word.ends_with(suffix))
+                && !has_related_words(word, &all_words)
+            {
+                reasons.push(SuspicionReason::LikelyProperNoun);
+            }
+
+            (!reasons.is_empty()).then(|| SuspiciousWord {
+                word: word.to_owned(),
+                reasons,
+            })
+        })
+        .collect()
+}
+
+fn get_bigrams(word: &str) -> impl Iterator<Item = (char, char)> + '_ {
+    word.chars().zip(word.chars().skip(1))
+}
+
+///
+/// Vowels with accents count as vowels too. Ex: 'é'
+///
+fn is_vowel(letter: char, include_y: bool) -> bool {
+    let base_letter = letter.nfd().next().unwrap_or(letter);
+    matches!(base_letter, 'a' | 'e' | 'i' | 'o' | 'u')
+        || (include_y && base_letter == 'y')
+}
+
+///
+/// Checks if a word is a valid roman numeral. Ex: "xiii"
+///
+fn is_roman_numeral(word: &str) -> bool {
+    let numerals = [
+        ["", "m", "mm", "mmm"].as_slice(),
+        &["", "c", "cc", "ccc", "cd", "d", "dc", "dcc", "dccc", "cm"],
+        &["", "x", "xx", "xxx", "xl", "l", "lx", "lxx", "lxxx", "xc"],
+        &["", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"],
+    ];
+    let mut remaining = word;
+    for place in numerals {
+        // Try the longest numeral of this place first
+        if let Some(numeral) = place
+            .iter()
+            .filter(|numeral| remaining.starts_with(*numeral))
+            .max_by_key(|numeral| numeral.len())
+        {
+            remaining = &remaining[numeral.len()..];
+        }
+    }
+    remaining.is_empty()
+}
+
+///
+/// Checks if the dictionary has any words related to this one, such
+/// as a plural ("cats" for "cat") or a root word ("cat" for "cats").
+/// Names and places usually don't have any.
+///
+fn has_related_words(word: &str, all_words: &HashSet<&str>) -> bool {
+    FAMILY_SUFFIXES.iter().any(|suffix| {
+        all_words.contains(format!("{word}{suffix}").as_str())
+            || word.strip_suffix(suffix).is_some_and(|root| {
+                all_words.contains(root)
+                    || all_words.contains(format!("{root}e").as_str())
+                    || all_words.contains(format!("{root}y").as_str())
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_words(words: &str) -> Vec<String> {
+        words.split(' ').map(|word| word.to_owned()).collect()
+    }
+
+    fn reasons_for<'a>(
+        suspicious_words: &'a [SuspiciousWord],
+        word: &str,
+    ) -> &'a [SuspicionReason] {
+        suspicious_words
+            .iter()
+            .find(|suspicious_word| suspicious_word.word == word)
+            .map(|suspicious_word| suspicious_word.reasons.as_slice())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_find_suspicious_words() {
+        let words = to_words(
+            "boston crypt kcmg xxiii mixes mixed button buttons \
+             sample samples simple simpler",
+        );
+        let suspicious_words = find_suspicious_words(&words, Language::English);
+
+        assert_eq!(
+            reasons_for(&suspicious_words, "kcmg")[..2],
+            [
+                SuspicionReason::NoVowels,
+                SuspicionReason::RareBigram("kc".to_owned())
+            ]
+        );
+        assert!(reasons_for(&suspicious_words, "crypt")
+            .contains(&SuspicionReason::NoVowels));
+        assert!(!reasons_for(&suspicious_words, "crypt")
+            .contains(&SuspicionReason::LikelyAbbreviation));
+        assert!(reasons_for(&suspicious_words, "xxiii")
+            .contains(&SuspicionReason::TripleLetter));
+        assert!(reasons_for(&suspicious_words, "xxiii")
+            .contains(&SuspicionReason::LikelyAbbreviation));
+        assert!(reasons_for(&suspicious_words, "boston")
+            .contains(&SuspicionReason::LikelyProperNoun));
+        assert!(!reasons_for(&suspicious_words, "button")
+            .contains(&SuspicionReason::LikelyProperNoun));
+    }
+
+    #[test]
+    fn test_is_roman_numeral() {
+        assert!(is_roman_numeral("xxiii"));
+        assert!(is_roman_numeral("mcmxc"));
+        assert!(!is_roman_numeral("civic"));
+        assert!(!is_roman_numeral("iiii"));
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashSet;
+
+use colored::Colorize;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::dictionary::{
+    read_clean_dictionary, read_precomputed_file, sorted_letters_missing_from,
+    WILDCARD_LETTER,
+};
+use crate::generator::GeneratedBoard;
+
+///
+/// A previously generated board which would come out differently
+/// with the new dictionary.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AffectedBoard {
+    /// The index of the board within the boards file
+    pub index: usize,
+    pub letters: String,
+    /// Words placed on the board which were removed
+    pub removed_placed_words: Vec<String>,
+    /// Words of the board's `all` list which were removed
+    pub removed_words: Vec<String>,
+    /// New words which can be built from the board's letters,
+    /// and would be added to its `all` list
+    pub added_words: Vec<String>,
+    /// The board's starting word was removed from the precomputed
+    /// words, so it would never be generated again
+    pub removed_starting_word: bool,
+}
+
+///
+/// The words of one build of the dictionary.
+///
+#[derive(Clone, Debug, Default)]
+pub struct DictionaryWords {
+    pub words: HashSet<String>,
+    /// The starting words, which only a precomputed words file has
+    pub starting_words: Option<HashSet<String>>,
+}
+
+///
+/// The differences between two builds of the dictionary, and
+/// which existing boards they affect.
+///
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DictionaryDiff {
+    pub added_words: Vec<String>,
+    pub removed_words: Vec<String>,
+    /// Only compared when both builds are precomputed words files
+    pub added_starting_words: Vec<String>,
+    pub removed_starting_words: Vec<String>,
+    pub affected_boards: Vec<AffectedBoard>,
+}
+
+// ===============Static Methods=================
+
+impl DictionaryDiff {
+    ///
+    /// Compares the words of an old and a new dictionary, then checks
+    /// every board to see if it would be affected by the changes.
+    ///
+    pub fn new(
+        old_words: &DictionaryWords,
+        new_words: &DictionaryWords,
+        boards: &[GeneratedBoard],
+    ) -> Self {
+        let (added_words, removed_words) =
+            diff_words(&old_words.words, &new_words.words);
+        let (added_starting_words, removed_starting_words) =
+            match (&old_words.starting_words, &new_words.starting_words) {
+                (Some(old_starting_words), Some(new_starting_words)) => {
+                    diff_words(old_starting_words, new_starting_words)
+                }
+                _ => (Vec::new(), Vec::new()),
+            };
+
+        let affected_boards = boards
+            .iter()
+            .enumerate()
+            .filter_map(|(index, board)| {
+                let affected_board = AffectedBoard {
+                    index,
+                    letters: board.letters.to_owned(),
+                    removed_placed_words: board
+                        .used
+                        .keys()
+                        .filter(|word| {
+                            removed_words.binary_search(word).is_ok()
+                        })
+                        .cloned()
+                        .sorted()
+                        .collect(),
+                    removed_words: board
+                        .all
+                        .split('_')
+                        .filter(|word| {
+                            removed_words
+                                .binary_search(&word.to_string())
+                                .is_ok()
+                        })
+                        .map(|word| word.to_owned())
+                        .collect(),
+                    added_words: added_words
+                        .iter()
+                        .filter(|word| can_be_built_from(word, &board.letters))
+                        .cloned()
+                        .collect(),
+                    removed_starting_word: removed_starting_words
+                        .binary_search(&board.letters)
+                        .is_ok(),
+                };
+                let is_affected =
+                    !affected_board.removed_placed_words.is_empty()
+                        || !affected_board.removed_words.is_empty()
+                        || !affected_board.added_words.is_empty()
+                        || affected_board.removed_starting_word;
+                is_affected.then_some(affected_board)
+            })
+            .collect();
+
+        Self {
+            added_words,
+            removed_words,
+            added_starting_words,
+            removed_starting_words,
+            affected_boards,
+        }
+    }
+
+    ///
+    /// Reads every word out of either a clean dictionary file (.txt),
+    /// or a precomputed words file (.json). Only the precomputed words
+    /// file has starting words.
+    ///
+    pub fn read_dictionary_words(filepath: &str) -> DictionaryWords {
+        if !filepath.ends_with(".json") {
+            return DictionaryWords {
+                words: read_clean_dictionary(filepath).into_iter().collect(),
+                starting_words: None,
+            };
+        }
+
+        let precomputed_words = read_precomputed_file(filepath)
+            .unwrap_or_else(|| panic!("Could not read '{filepath}'!"));
+        DictionaryWords {
+            starting_words: Some(precomputed_words.keys().cloned().collect()),
+            words: precomputed_words
+                .into_iter()
+                .flat_map(|(starting_word, subwords)| {
+                    subwords.into_iter().chain([starting_word])
+                })
+                .collect(),
+        }
+    }
+}
+
+// ===============Instance Methods===============
+
+impl DictionaryDiff {
+    ///
+    /// Prints the differences in a (human readable) format
+    ///
+    pub fn print_table(&self) {
+        let blank_line = "                                       ".on_cyan();
+        println!("{blank_line}");
+        println!("{}", "            Dictionary Diff            ".on_cyan());
+        println!("{blank_line}");
+
+        println!(
+            "{} {:?}",
+            format!("Added ({}):", self.added_words.len()).green(),
+            self.added_words
+        );
+        println!(
+            "{} {:?}",
+            format!("Removed ({}):", self.removed_words.len()).red(),
+            self.removed_words
+        );
+        if !self.added_starting_words.is_empty()
+            || !self.removed_starting_words.is_empty()
+        {
+            println!(
+                "{} {:?}",
+                format!(
+                    "Added starting words ({}):",
+                    self.added_starting_words.len()
+                )
+                .green(),
+                self.added_starting_words
+            );
+            println!(
+                "{} {:?}",
+                format!(
+                    "Removed starting words ({}):",
+                    self.removed_starting_words.len()
+                )
+                .red(),
+                self.removed_starting_words
+            );
+        }
+        println!("{blank_line}");
+
+        println!(
+            "{}",
+            format!("Affected Boards ({}):", self.affected_boards.len()).bold()
+        );
+        for board in self.affected_boards.iter() {
+            let placed_marker = match board.removed_placed_words.is_empty() {
+                true => String::new(),
+                false => "  <- places a removed word".red().to_string(),
+            };
+            println!("#{} '{}'{placed_marker}", board.index, board.letters);
+            if !board.removed_placed_words.is_empty() {
+                println!(
+                    "    placed & removed: {:?}",
+                    board.removed_placed_words
+                );
+            }
+            if !board.removed_words.is_empty() {
+                println!("    removed from all: {:?}", board.removed_words);
+            }
+            if !board.added_words.is_empty() {
+                println!("    added to all:     {:?}", board.added_words);
+            }
+            if board.removed_starting_word {
+                println!("    its starting word was removed");
+            }
+        }
+        println!("{blank_line}");
+    }
+}
+
+///
+/// Returns the words which were added, and the words which were
+/// removed, both sorted.
+///
+fn diff_words(
+    old_words: &HashSet<String>,
+    new_words: &HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    (
+        new_words.difference(old_words).cloned().sorted().collect(),
+        old_words.difference(new_words).cloned().sorted().collect(),
+    )
+}
+
+///
+/// Checks if a word can be built from the letters of a board's
+/// wheel (which may include wildcards).
+///
+fn can_be_built_from(word: &str, letters: &str) -> bool {
+    let sorted_word: Vec<char> = word.chars().sorted().collect();
+    let (wildcards, letters): (Vec<char>, Vec<char>) = letters
+        .chars()
+        .partition(|letter| *letter == WILDCARD_LETTER);
+    let sorted_letters: Vec<char> = letters.into_iter().sorted().collect();
+
+    sorted_letters_missing_from(&sorted_word, &sorted_letters).len()
+        <= wildcards.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_misuses_board;
+    use std::collections::HashMap;
+
+    fn to_words(words: &str) -> HashSet<String> {
+        words.split(' ').map(|word| word.to_owned()).collect()
+    }
+
+    fn to_dictionary_words(
+        words: &str,
+        starting_words: Option<&str>,
+    ) -> DictionaryWords {
+        DictionaryWords {
+            words: to_words(words),
+            starting_words: starting_words.map(to_words),
+        }
+    }
+
+    #[test]
+    fn test_dictionary_diff() {
+        let old_words =
+            to_dictionary_words("emus mess misuses muse sums bail", None);
+        let new_words =
+            to_dictionary_words("emus misuses muse sums uses bail", None);
+        let board = make_misuses_board();
+        let unaffected_board = GeneratedBoard {
+            letters: "mailbox".to_owned(),
+            used: HashMap::new(),
+            all: "bail".to_owned(),
+            ..board.clone()
+        };
+
+        let diff = DictionaryDiff::new(
+            &old_words,
+            &new_words,
+            &[unaffected_board, board],
+        );
+
+        assert_eq!(diff.added_words, vec!["uses"]);
+        assert_eq!(diff.removed_words, vec!["mess"]);
+        assert_eq!(
+            diff.affected_boards,
+            vec![AffectedBoard {
+                index: 1,
+                letters: "misuses".to_owned(),
+                removed_placed_words: vec!["mess".to_owned()],
+                removed_words: vec!["mess".to_owned()],
+                added_words: vec!["uses".to_owned()],
+                removed_starting_word: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dictionary_diff_of_starting_words() {
+        // "misuses" is still a word, but it's no longer a starting word
+        let words = "emus mess misuses muse sums bail mailbox";
+        let old_words = to_dictionary_words(words, Some("misuses"));
+        let new_words = to_dictionary_words(words, Some("mailbox"));
+        let board = make_misuses_board();
+
+        let diff = DictionaryDiff::new(&old_words, &new_words, &[board]);
+
+        assert!(diff.added_words.is_empty() && diff.removed_words.is_empty());
+        assert_eq!(diff.added_starting_words, vec!["mailbox"]);
+        assert_eq!(diff.removed_starting_words, vec!["misuses"]);
+        assert_eq!(diff.affected_boards.len(), 1);
+        assert!(diff.affected_boards[0].removed_starting_word);
+
+        // A clean dictionary doesn't have starting words to compare
+        let clean_words = to_dictionary_words(words, None);
+        let diff = DictionaryDiff::new(
+            &old_words,
+            &clean_words,
+            &[make_misuses_board()],
+        );
+        assert!(diff.removed_starting_words.is_empty());
+        assert!(diff.affected_boards.is_empty());
+    }
+}
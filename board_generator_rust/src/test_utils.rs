@@ -0,0 +1,92 @@
+use itertools::Itertools;
+
+use crate::board::{BoardManager, Direction};
+use crate::dictionary::DictionaryManager;
+use crate::generator::{
+    GeneratedBoard, WordLocationOnBoard, WordLocationsOnBoard,
+};
+
+///
+/// The locations of each word, without placing them on a board.
+///
+/// Example:
+/// ```
+/// let used = make_used(&[("mess", 0, 0, Direction::H)]);
+/// ```
+///
+pub fn make_used(
+    words: &[(&str, usize, usize, Direction)],
+) -> WordLocationsOnBoard {
+    words
+        .iter()
+        .map(|(word, row, col, direction)| {
+            let location = WordLocationOnBoard {
+                row: *row,
+                col: *col,
+                direction: *direction,
+            };
+            (word.to_string(), location)
+        })
+        .collect()
+}
+
+///
+/// Places the words on a `size` x `size` board. Every placed word is in
+/// `all`, and the first word is used as the `letters`. Anything else
+/// can be changed with struct update syntax.
+///
+/// Example:
+/// ```
+/// let board = GeneratedBoard {
+///     letters: "seat".to_owned(),
+///     ..make_board(5, &[("sat", 0, 0, Direction::H)])
+/// };
+/// ```
+///
+pub fn make_board(
+    size: usize,
+    words: &[(&str, usize, usize, Direction)],
+) -> GeneratedBoard {
+    let mut bm = BoardManager::new();
+    bm.new_list(size);
+    for (word, row, col, direction) in words.iter() {
+        bm.place_word(word, *row, *col, *direction);
+    }
+    GeneratedBoard {
+        grid: bm.get_grid_str(),
+        used: bm.get_placed_words_for_serialization(),
+        all: words.iter().map(|(word, ..)| word).sorted().join("_"),
+        letters: words
+            .first()
+            .map(|(word, ..)| *word)
+            .unwrap_or_default()
+            .to_owned(),
+        language: "en".to_owned(),
+        ..GeneratedBoard::default()
+    }
+}
+
+///
+/// "misuses" across the top of a 7x7 board, and "mess" down from its "m"
+///
+pub fn make_misuses_board() -> GeneratedBoard {
+    GeneratedBoard {
+        all: "emus_mess_muse_sums".to_owned(),
+        ..make_board(
+            7,
+            &[
+                ("misuses", 0, 0, Direction::H),
+                ("mess", 0, 0, Direction::V),
+            ],
+        )
+    }
+}
+
+///
+/// A dictionary of the (space separated) words
+///
+pub fn make_dictionary(words: &str) -> DictionaryManager {
+    let mut dictionary = DictionaryManager::new();
+    dictionary.load_words(words.split(' ').map(String::from).collect());
+    dictionary
+}
@@ -1,13 +1,53 @@
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use std::cell::RefCell;
+
+use rand::{seq::SliceRandom, thread_rng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use unicode_segmentation::UnicodeSegmentation;
+
+thread_local! {
+    /// When set, every random choice made on this thread comes from
+    /// this generator instead, so the results can be repeated.
+    static SEEDED_RNG: RefCell<Option<ChaCha8Rng>> = const { RefCell::new(None) };
+}
+
+///
+/// Makes every random choice on this thread repeatable, by drawing
+/// them from a generator with the given seed. Passing `None` goes
+/// back to (truly) random choices.
+///
+/// Example:
+/// ```
+/// set_random_seed(Some(42));
+/// let first = get_random_int_in_range(0, 100);
+/// set_random_seed(Some(42));
+/// // first == second
+/// let second = get_random_int_in_range(0, 100);
+/// set_random_seed(None);
+/// ```
+///
+pub fn set_random_seed(seed: Option<u64>) {
+    SEEDED_RNG.with(|seeded_rng| {
+        *seeded_rng.borrow_mut() = seed.map(ChaCha8Rng::seed_from_u64);
+    });
+}
+
+///
+/// Runs `action` with this thread's random number generator. Use this
+/// for any random choice that should respect `set_random_seed()`.
+///
+pub fn with_rng<T>(action: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    SEEDED_RNG.with(|seeded_rng| match seeded_rng.borrow_mut().as_mut() {
+        Some(rng) => action(rng),
+        None => action(&mut thread_rng()),
+    })
+}
 
 pub fn get_random_int_in_range(min_num: usize, max_num: usize) -> usize {
-    let mut rng = rand::thread_rng();
-    rng.gen_range(min_num..max_num)
+    with_rng(|rng| rng.gen_range(min_num..max_num))
 }
 
 pub fn get_random_float_in_range(min_num: f32, max_num: f32) -> f32 {
-    let mut rng = rand::thread_rng();
-    rng.gen_range(min_num..max_num)
+    with_rng(|rng| rng.gen_range(min_num..max_num))
 }
 
 ///
@@ -30,6 +70,39 @@ pub fn all_indicies_of(word: &str, letter_to_find: char) -> Vec<usize> {
     indicies
 }
 
+///
+/// The number of letters in a word, as a player would count them.
+/// `String::len()` counts bytes, so "niño" would be 5 letters.
+///
+/// Example:
+/// ```
+/// // 4
+/// let length = word_length("niño");
+/// ```
+///
+pub fn word_length(word: &str) -> usize {
+    word.graphemes(true).count()
+}
+
+///
+/// Escapes the characters that have a meaning in HTML and SVG
+/// (XML), so the text can be put between tags or inside an
+/// attribute.
+///
+/// Example:
+/// ```
+/// // "Tom &amp; Jerry&#39;s &lt;3"
+/// let escaped = escape_html("Tom & Jerry's <3");
+/// ```
+///
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
 ///
 /// Randomizes the order of the elements of a vector.
 ///
@@ -44,8 +117,7 @@ pub fn all_indicies_of(word: &str, letter_to_find: char) -> Vec<usize> {
 ///
 pub fn shuffle<T: std::clone::Clone>(list: &[T]) -> Vec<T> {
     let mut shuffled_list: Vec<T> = list.to_vec();
-    let mut rng = thread_rng();
-    shuffled_list.shuffle(&mut rng);
+    with_rng(|rng| shuffled_list.shuffle(rng));
     shuffled_list
 }
 
@@ -61,4 +133,34 @@ mod tests {
         assert_eq!(indices_matches, vec![1, 4]);
         assert_eq!(indices_nomatches, Vec::<usize>::new());
     }
+
+    #[test]
+    fn test_word_length() {
+        assert_eq!(word_length("nathan"), 6);
+        assert_eq!(word_length("niño"), 4);
+        assert_eq!(word_length("stra\u{df}e"), 6);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html("nathan"), "nathan");
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_set_random_seed() {
+        let numbers: Vec<usize> = (0..100).collect();
+
+        set_random_seed(Some(42));
+        let first = shuffle(&numbers);
+        set_random_seed(Some(42));
+        let second = shuffle(&numbers);
+        set_random_seed(None);
+
+        assert_eq!(first, second);
+        assert_ne!(first, numbers);
+    }
 }